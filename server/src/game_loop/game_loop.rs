@@ -1,8 +1,20 @@
-use crate::messages::{OutgoingEvent, OutgoingMessage};
-use crate::models::{Item, ItemKind, ItemTier, Slot, SlotKind};
+use crate::messages::{CombatHit, Combatant, MonsterEncounter, MonsterKilled, OutgoingEvent, OutgoingMessage};
+use crate::meta::RESPAWN_HP_PERCENT;
+use crate::models::{Expedition, ExpeditionEndReason, ExpeditionKind};
 use crate::server::GameServer;
 use crate::server::WebSocketManager;
+use crate::services::buffs::Buffs;
+use crate::services::combat::{Combat, CombatState, Encounter, MONSTER_SPAWN_DELAY};
+use crate::services::expedition_service::ExpeditionService;
+use crate::services::item_destroyer::ItemDestroyer;
+use crate::services::loot_generator::LootGenerator;
+use crate::services::market::Market;
+use crate::services::offline_progress::OfflineProgress;
+use crate::services::parties::Parties;
 use crate::services::probability_calculator::PlayerProbabilities;
+use crate::services::rewards::Rewards;
+use crate::services::salvage::Salvage;
+use crate::services::shop::Shop;
 use chrono::Utc;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -26,15 +38,26 @@ impl GameLoop {
         self.running = true;
 
         let mut player_regen_timers: HashMap<Uuid, Instant> = HashMap::new();
+        let mut hp_regen_timers: HashMap<Uuid, Instant> = HashMap::new();
         let mut expedition_timers: HashMap<Uuid, Instant> = HashMap::new();
+        let mut combat_states: HashMap<Uuid, CombatState> = HashMap::new();
         let mut tick_interval = interval(Duration::from_millis(50));
 
-        while self.running {
+        loop {
             tick_interval.tick().await;
             let now = Instant::now();
 
             self.handle_expedition(&mut expedition_timers, now).await;
+            self.handle_combat(&mut combat_states, now).await;
             self.handle_energy_regeneration(&mut player_regen_timers, now).await;
+            self.handle_hp_regeneration(&mut hp_regen_timers, now).await;
+            self.handle_respawns().await;
+            self.handle_buff_expiry().await;
+            self.handle_shop_restock().await;
+            self.handle_market_expiry().await;
+            self.handle_salvage_expiry();
+            self.handle_destroy_confirmation_expiry();
+            self.handle_party_invite_expiry().await;
         }
     }
 
@@ -43,7 +66,6 @@ impl GameLoop {
         let ws_manager = WebSocketManager::global();
 
         let active_expeditions = server.expeditions_store.find_all_by(|e| e.ended_at.is_none());
-        let now = Instant::now();
 
         for expedition in active_expeditions {
             let last_tick = expedition_timers
@@ -55,75 +77,58 @@ impl GameLoop {
                 let elapsed_secs = (Utc::now() - expedition.started_at).num_seconds() as u64;
 
                 for player_id in &expedition.participants {
+                    if !ws_manager.is_connected(*player_id) {
+                        let _ = OfflineProgress::checkpoint(*player_id);
+                        continue;
+                    }
+
                     ws_manager.send_to_player(*player_id, OutgoingMessage::new(
                         OutgoingEvent::ExpeditionCountup,
                         Box::new(elapsed_secs) as Box<dyn erased_serde::Serialize + Send>,
                     )).await;
 
-                    if let Some(player_resource) = server.player_resource_store.find_by(|r| r.player_id == *player_id) {
-                        if player_resource.energy > 0 {
-                            let energy_cost = Self::calculate_energy_cost(player_id.clone()).await;
+                    if let Some(player_resource) = server.player_resource_store.find_by(|r| r.player_id == *player_id)
+                        && player_resource.energy > 0 {
+                        let energy_cost = ExpeditionService::energy_cost(*player_id, &expedition.kind);
 
-                            let updated_resource = server.player_resource_store.update(&player_resource.id, |resource| {
-                                resource.energy = resource.energy.saturating_sub(energy_cost);
+                        let updated_resource = server.player_resource_store.update(&player_resource.id, |resource| {
+                            resource.energy = resource.energy.saturating_sub(energy_cost);
+                        });
+
+                        if let Ok(updated) = updated_resource {
+                            ExpeditionService::record(*player_id, |summary| {
+                                summary.energy_spent += player_resource.energy - updated.energy;
                             });
 
-                            if let Ok(updated) = updated_resource {
-                                ws_manager.send_to_player(*player_id, OutgoingMessage::new(
-                                    OutgoingEvent::PlayerResource,
-                                    Box::new(updated.clone()) as Box<dyn erased_serde::Serialize + Send>,
-                                )).await;
-
-                                if updated.energy == 0 {
-                                    let _ = server.expeditions_store.update(&expedition.id, |exp| {
-                                        exp.ended_at = Some(Utc::now());
-                                    });
-
-                                    let ground_slots = server.slots_store.find_all_by(|slot| {
-                                        slot.player_id == *player_id && slot.kind == SlotKind::Ground
-                                    });
-
-                                    for slot in ground_slots {
-                                        let _ = server.slots_store.update(&slot.id, |s| {
-                                            s.item = None;
-                                        });
-                                    }
-
-                                    let mut all_slots = server.slots_store.find_all_by(|slot| slot.player_id == *player_id);
-                                    all_slots.sort_by_key(|slot| slot.index);
-
-                                    if let Some(player_state) = server.player_state_store.find_by(|state| state.player_id == *player_id) {
-                                        let updated_state = server.player_state_store.update(&player_state.id, |state| {
-                                            state.is_looting = false;
-                                        });
-
-                                        if let Ok(updated) = updated_state {
-                                            ws_manager.send_to_player(*player_id, OutgoingMessage::new(
-                                                OutgoingEvent::PlayerState,
-                                                Box::new(updated) as Box<dyn erased_serde::Serialize + Send>,
-                                            )).await;
-                                        }
-                                    }
-
-                                    ws_manager.send_to_player(*player_id, OutgoingMessage::new(
-                                        OutgoingEvent::Slots,
-                                        Box::new(all_slots) as Box<dyn erased_serde::Serialize + Send>,
-                                    )).await;
-
-                                    ws_manager.send_to_player(*player_id, OutgoingMessage::new(
-                                        OutgoingEvent::ExpeditionCountup,
-                                        Box::new(-1) as Box<dyn erased_serde::Serialize + Send>,
-                                    )).await;
-
-                                    ws_manager.send_log_to_player(*player_id, "Your expedition ended due to lack of energy.".to_string()).await;
-                                }
+                            ws_manager.send_to_player(*player_id, OutgoingMessage::new(
+                                OutgoingEvent::PlayerResource,
+                                Box::new(updated.clone()) as Box<dyn erased_serde::Serialize + Send>,
+                            )).await;
+
+                            if updated.energy == 0 {
+                                let _ = ExpeditionService::end(
+                                    expedition.id,
+                                    *player_id,
+                                    ExpeditionEndReason::OutOfEnergy,
+                                    "Your expedition ended due to lack of energy.",
+                                ).await;
                             }
                         }
                     }
 
-                    Self::handle_exp_rolls(*player_id, elapsed_secs).await;
-                    Self::handle_cin_rolls(*player_id, elapsed_secs).await;
-                    Self::handle_auto_looting(*player_id).await;
+                    match expedition.kind {
+                        ExpeditionKind::Hunt => {
+                            Self::handle_exp_rolls(*player_id, elapsed_secs).await;
+                            Self::handle_cin_rolls(*player_id, elapsed_secs).await;
+                            Self::handle_loot_rolls(*player_id, elapsed_secs, &expedition).await;
+                        }
+                        ExpeditionKind::Gathering | ExpeditionKind::Mining | ExpeditionKind::Fishing => {
+                            Self::handle_exp_rolls(*player_id, elapsed_secs).await;
+                            Self::handle_material_rolls(*player_id, elapsed_secs, &expedition).await;
+                        }
+                    }
+
+                    Rewards::auto_loot(*player_id).await;
                 }
 
                 expedition_timers.insert(expedition.id, now);
@@ -146,6 +151,14 @@ impl GameLoop {
                 continue;
             }
 
+            let is_offline = server.player_state_store
+                .find_by(|state| state.player_id == player_resource.player_id)
+                .is_some_and(|state| state.offline_since.is_some());
+
+            if is_offline {
+                continue;
+            }
+
             let player_stats = server.player_stats_store
                 .find_by(|stats| stats.player_id == player_resource.player_id);
 
@@ -176,193 +189,356 @@ impl GameLoop {
         }
     }
 
-    async fn handle_exp_rolls(player_id: Uuid, elapsed_secs: u64) {
+    async fn handle_hp_regeneration(&self, hp_regen_timers: &mut HashMap<Uuid, Instant>, now: Instant) {
         let server = GameServer::global();
         let ws_manager = WebSocketManager::global();
 
-        let exp_frequency = player_id.exp_frequency();
+        let players_with_resources: Vec<_> = server.player_resource_store
+            .data
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect();
 
-        if elapsed_secs % exp_frequency == 0 {
-            let exp_chance = player_id.exp_chance();
-            let roll = rand::random::<f32>();
+        for player_resource in players_with_resources {
+            if player_resource.hp >= player_resource.max_hp {
+                continue;
+            }
 
-            if roll < exp_chance {
-                let exp_amount = player_id.exp_amount();
+            let regen_paused = server.player_state_store
+                .find_by(|state| state.player_id == player_resource.player_id)
+                .is_some_and(|state| state.respawn_at.is_some() || state.offline_since.is_some());
+
+            if regen_paused {
+                continue;
+            }
 
-                if let Some(player) = server.player_store.find_by(|p| p.id == player_id) {
-                    let updated_player = server.player_store.update(&player.id, |p| {
-                        p.exp += exp_amount;
-                    });
+            let player_stats = server.player_stats_store
+                .find_by(|stats| stats.player_id == player_resource.player_id);
 
-                    if let Ok(updated) = updated_player {
-                        ws_manager.send_to_player(player_id, OutgoingMessage::new(
-                            OutgoingEvent::PlayerInfo,
-                            Box::new(updated) as Box<dyn erased_serde::Serialize + Send>,
-                        )).await;
+            if let Some(stats) = player_stats {
+                let regen_interval = Duration::from_millis(stats.hp_regeneration_interval);
 
-                        ws_manager.send_to_player(player_id, OutgoingMessage::new(
-                            OutgoingEvent::GainedExperience,
-                            Box::new(exp_amount) as Box<dyn erased_serde::Serialize + Send>,
-                        )).await;
+                let should_regen = hp_regen_timers
+                    .get(&player_resource.player_id)
+                    .map(|last_regen| now.duration_since(*last_regen) >= regen_interval)
+                    .unwrap_or(true);
 
-                        ws_manager.send_log_to_player(
-                            player_id,
-                            format!("You gained {} experience!", exp_amount),
-                        ).await;
+                if should_regen {
+                    if let Ok(updated_resource) = server.player_resource_store.update(&player_resource.id, |resource| {
+                        resource.hp = (resource.hp + stats.hp_regeneration).min(resource.max_hp);
+                    }) {
+                        ws_manager.send_to_player(player_resource.player_id, OutgoingMessage::new(
+                            OutgoingEvent::PlayerResource,
+                            Box::new(updated_resource) as Box<dyn erased_serde::Serialize + Send>,
+                        )).await;
                     }
+
+                    hp_regen_timers.insert(player_resource.player_id, now);
                 }
             }
         }
     }
 
-    async fn handle_cin_rolls(player_id: Uuid, elapsed_secs: u64) {
+    async fn handle_respawns(&self) {
         let server = GameServer::global();
         let ws_manager = WebSocketManager::global();
 
+        let respawned = server.player_state_store
+            .find_all_by(|state| state.respawn_at.is_some_and(|respawn_at| respawn_at <= Utc::now()));
+
+        for player_state in respawned {
+            let Ok(updated_state) = server.player_state_store.update(&player_state.id, |state| {
+                state.respawn_at = None;
+            }) else {
+                continue;
+            };
+
+            ws_manager.send_to_player(player_state.player_id, OutgoingMessage::new(
+                OutgoingEvent::PlayerState,
+                Box::new(updated_state) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+
+            if let Some(resource) = server.player_resource_store.find_by(|r| r.player_id == player_state.player_id)
+                && let Ok(updated_resource) = server.player_resource_store.update(&resource.id, |resource| {
+                    resource.hp = resource.hp.max(resource.max_hp * RESPAWN_HP_PERCENT / 100);
+                }) {
+                ws_manager.send_to_player(player_state.player_id, OutgoingMessage::new(
+                    OutgoingEvent::PlayerResource,
+                    Box::new(updated_resource) as Box<dyn erased_serde::Serialize + Send>,
+                )).await;
+            }
+
+            ws_manager.send_log_to_player(
+                player_state.player_id,
+                "You have recovered and can set out on an expedition again.".to_string(),
+            ).await;
+        }
+    }
+
+    async fn handle_buff_expiry(&self) {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let expired = server.buffs_store.find_all_by(|buff| buff.is_expired());
+
+        for buff in expired {
+            if server.buffs_store.remove(&buff.id).is_err() {
+                continue;
+            }
+
+            if buff.kind.affects_stats()
+                && let Some(stats) = server.player_stats_store.find_by(|s| s.player_id == buff.player_id) {
+                let _ = stats.recalculate();
+            }
+
+            Buffs::send(buff.player_id).await;
+
+            ws_manager.send_log_to_player(buff.player_id, format!("{:?} buff has worn off.", buff.kind)).await;
+        }
+    }
+
+    async fn handle_shop_restock(&self) {
+        if Shop::restock() {
+            Shop::broadcast_listings().await;
+        }
+    }
+
+    async fn handle_market_expiry(&self) {
+        for listing in Market::expired() {
+            let body = format!(
+                "Your listing of {} {} expired without a buyer.",
+                listing.item.quantity, listing.item.name
+            );
+
+            let _ = Market::return_to_seller(listing, "Listing expired", body).await;
+        }
+    }
+
+    fn handle_salvage_expiry(&self) {
+        Salvage::expire();
+    }
+
+    fn handle_destroy_confirmation_expiry(&self) {
+        ItemDestroyer::expire_confirmations();
+    }
+
+    async fn handle_party_invite_expiry(&self) {
+        Parties::expire_invites().await;
+    }
+
+    async fn handle_exp_rolls(player_id: Uuid, elapsed_secs: u64) {
+        let exp_frequency = player_id.exp_frequency();
+
+        if elapsed_secs.is_multiple_of(exp_frequency) {
+            let exp_chance = player_id.exp_chance();
+            let roll = rand::random::<f32>();
+
+            if roll < exp_chance {
+                let exp_amount = player_id.exp_amount();
+
+                Rewards::grant_exp(player_id, exp_amount).await;
+            }
+        }
+    }
+
+    async fn handle_cin_rolls(player_id: Uuid, elapsed_secs: u64) {
         let cin_frequency = player_id.cin_frequency();
 
-        if elapsed_secs % cin_frequency == 0 {
+        if elapsed_secs.is_multiple_of(cin_frequency) {
             let cin_chance = player_id.cin_chance();
             let roll = rand::random::<f32>();
 
             if roll < cin_chance {
                 let cin_amount = player_id.cin_amount();
 
-                let cin_item = Item::new(
-                    player_id,
-                    ItemKind::Currency,
-                    "Cin",
-                    ItemTier::Common,
-                    "game-icons:two-coins",
-                    cin_amount,
-                    0,
-                    0,
-                    "The primary currency of the realm.",
-                    0.01,
-                    true,
-                    false,
-                    None,
-                );
-
-                match cin_item.add_to_empty_slot(SlotKind::Ground) {
-                    Ok(_) => {
-                        if let Ok(_) = server.items_store.insert(cin_item) {
-                            let mut slots = server.slots_store.find_all_by(|slot| slot.player_id == player_id);
-                            slots.sort_by_key(|slot| slot.index);
-
-                            ws_manager.send_to_player(player_id, OutgoingMessage::new(
-                                OutgoingEvent::Slots,
-                                Box::new(slots) as Box<dyn erased_serde::Serialize + Send>,
-                            )).await;
-
-                            ws_manager.send_to_player(player_id, OutgoingMessage::new(
-                                OutgoingEvent::GainedCin,
-                                Box::new(cin_amount) as Box<dyn erased_serde::Serialize + Send>,
-                            )).await;
-
-                            ws_manager.send_log_to_player(
-                                player_id,
-                                format!("You found {} cin!", cin_amount),
-                            ).await;
-                        }
-                    }
-                    Err(_) => {
-                        ws_manager.send_log_to_player(
-                            player_id,
-                            format!("You found {} cin but your backpack is full!", cin_amount),
-                        ).await;
-                    }
-                }
+                Rewards::grant_cin(player_id, cin_amount).await;
             }
         }
     }
 
-    async fn handle_auto_looting(player_id: Uuid) {
-        let server = GameServer::global();
-        let ws_manager = WebSocketManager::global();
+    async fn handle_loot_rolls(player_id: Uuid, elapsed_secs: u64, expedition: &Expedition) {
+        let loot_frequency = player_id.loot_frequency();
 
-        let player_state = server.player_state_store
-            .find_by(|state| state.player_id == player_id);
+        if elapsed_secs.is_multiple_of(loot_frequency) {
+            let loot_chance = player_id.loot_chance();
+            let roll = rand::random::<f32>();
 
-        let Some(state) = player_state else {
-            return;
-        };
+            if roll < loot_chance
+                && let Some(loot_item) = LootGenerator::roll(player_id, &expedition.kind, expedition.difficulty) {
+                Rewards::distribute_loot(player_id, vec![loot_item]).await;
+            }
+        }
+    }
+
+    async fn handle_material_rolls(player_id: Uuid, elapsed_secs: u64, expedition: &Expedition) {
+        let material_frequency = player_id.material_frequency(&expedition.kind);
 
-        if !state.is_looting {
+        if !elapsed_secs.is_multiple_of(material_frequency) {
             return;
         }
 
-        let ground_slots_with_items: Vec<Slot> = server.slots_store
-            .find_all_by(|slot| {
-                slot.player_id == player_id
-                    && slot.kind == SlotKind::Ground
-                    && slot.item.is_some()
-            });
+        if rand::random::<f32>() < player_id.material_chance(&expedition.kind)
+            && let Some(mut material) = LootGenerator::roll(player_id, &expedition.kind, expedition.difficulty) {
+            material.quantity *= player_id.material_amount();
+            Rewards::distribute_loot(player_id, vec![material]).await;
+        }
 
-        let mut slots_updated = false;
+        if rand::random::<f32>() < player_id.rare_find_chance(&expedition.kind)
+            && let Some(rare_find) = LootGenerator::roll_rare_find(player_id, &expedition.kind) {
+            Rewards::distribute_loot(player_id, vec![rare_find]).await;
+        }
+    }
 
-        for ground_slot in ground_slots_with_items {
-            if let Some(item) = &ground_slot.item {
-                match item.add_to_empty_slot(SlotKind::Inventory) {
-                    Ok(_) => {
-                        let _ = server.items_store.insert(item.clone());
+    async fn handle_combat(&self, combat_states: &mut HashMap<Uuid, CombatState>, now: Instant) {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
 
-                        let _ = server.slots_store.update(&ground_slot.id, |slot| {
-                            slot.item = None;
-                        });
+        let hunts = server.expeditions_store
+            .find_all_by(|e| e.ended_at.is_none() && e.kind == ExpeditionKind::Hunt);
 
-                        slots_updated = true;
+        let hunters: Vec<(Uuid, Expedition)> = hunts
+            .iter()
+            .flat_map(|expedition| {
+                expedition.participants.iter().map(|player_id| (*player_id, expedition.clone()))
+            })
+            .filter(|(player_id, _)| ws_manager.is_connected(*player_id))
+            .collect();
 
-                        ws_manager.send_log_to_player(
-                            player_id,
-                            format!("Looted {} {}", item.quantity, item.name),
-                        ).await;
-                    }
-                    Err(_) => {
-                        ws_manager.send_log_to_player(
-                            player_id,
-                            "Inventory is full.".to_string(),
-                        ).await;
-                    }
-                }
-            }
-        }
+        combat_states.retain(|player_id, _| hunters.iter().any(|(hunter, _)| hunter == player_id));
 
-        if slots_updated {
-            let mut all_slots = server.slots_store.find_all_by(|slot| slot.player_id == player_id);
-            all_slots.sort_by_key(|slot| slot.index);
+        for (player_id, expedition) in hunters {
+            let state = combat_states
+                .remove(&player_id)
+                .unwrap_or(CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY });
 
-            ws_manager.send_to_player(player_id, OutgoingMessage::new(
-                OutgoingEvent::Slots,
-                Box::new(all_slots) as Box<dyn erased_serde::Serialize + Send>,
-            )).await;
+            let next_state = Self::advance_combat(player_id, &expedition, state, now).await;
+            combat_states.insert(player_id, next_state);
         }
     }
 
-    async fn calculate_energy_cost(player_id: Uuid) -> u64 {
+    async fn advance_combat(player_id: Uuid, expedition: &Expedition, state: CombatState, now: Instant) -> CombatState {
         let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
 
-        let compass_slot = server.slots_store
-            .find_by(|slot| {
-                slot.player_id == player_id
-                    && slot.kind == SlotKind::Compass
-                    && slot.item.is_some()
-            });
+        let mut encounter = match state {
+            CombatState::Idle { next_spawn } if now < next_spawn => {
+                return CombatState::Idle { next_spawn };
+            }
+            CombatState::Idle { .. } => {
+                let player = server.player_store.find_by(|p| p.id == player_id);
+                let stats = server.player_stats_store.find_by(|s| s.player_id == player_id);
+
+                let (Some(player), Some(stats)) = (player, stats) else {
+                    return CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY };
+                };
+
+                let Some(encounter) = Combat::spawn(expedition, player.level, &stats, now) else {
+                    return CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY };
+                };
+
+                ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                    OutgoingEvent::MonsterEncounter,
+                    Box::new(MonsterEncounter {
+                        monster: encounter.monster.clone(),
+                        monster_hp: encounter.monster_hp,
+                    }) as Box<dyn erased_serde::Serialize + Send>,
+                )).await;
+
+                ws_manager.send_log_to_player(
+                    player_id,
+                    format!("A {} (level {}) attacks you!", encounter.monster.name, encounter.monster.level),
+                ).await;
 
-        if let Some(slot) = compass_slot {
-            if let Some(compass) = &slot.item {
-                let base_cost = 4u64;
+                return CombatState::Fighting(encounter);
+            }
+            CombatState::Fighting(encounter) => encounter,
+        };
 
-                let level_cost = (compass.level as f64 * 0.5).round() as u64;
+        let stats = server.player_stats_store.find_by(|s| s.player_id == player_id);
+        let resource = server.player_resource_store.find_by(|r| r.player_id == player_id);
 
-                let enchant_cost = (compass.enchanted as f64 * 0.3).round() as u64;
+        let (Some(stats), Some(mut resource)) = (stats, resource) else {
+            return CombatState::Fighting(encounter);
+        };
 
-                let total_cost = base_cost + level_cost + enchant_cost;
+        if now >= encounter.next_player_attack {
+            let damage = Combat::damage(stats.attack, encounter.monster.defense);
+            encounter.monster_hp = encounter.monster_hp.saturating_sub(damage);
+            encounter.next_player_attack = now + Duration::from_millis(stats.attack_speed);
 
-                return total_cost.max(4);
+            ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                OutgoingEvent::CombatHit,
+                Box::new(CombatHit {
+                    attacker: Combatant::Player,
+                    damage,
+                    monster_hp: encounter.monster_hp,
+                    player_hp: resource.hp,
+                }) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+
+            if encounter.monster_hp == 0 {
+                Self::handle_monster_killed(player_id, expedition, &encounter).await;
+                return CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY };
             }
         }
 
-        4
+        if now >= encounter.next_monster_attack {
+            let damage = Combat::damage(encounter.monster.attack, stats.defense);
+            encounter.next_monster_attack = now + Duration::from_millis(encounter.monster.attack_speed);
+
+            if let Ok(updated) = server.player_resource_store.update(&resource.id, |resource| {
+                resource.hp = resource.hp.saturating_sub(damage);
+            }) {
+                resource = updated;
+            }
+
+            ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                OutgoingEvent::CombatHit,
+                Box::new(CombatHit {
+                    attacker: Combatant::Monster,
+                    damage,
+                    monster_hp: encounter.monster_hp,
+                    player_hp: resource.hp,
+                }) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+
+            ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                OutgoingEvent::PlayerResource,
+                Box::new(resource.clone()) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+
+            if resource.hp == 0 {
+                let _ = ExpeditionService::handle_defeat(expedition.id, player_id, &encounter.monster).await;
+                return CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY };
+            }
+        }
+
+        CombatState::Fighting(encounter)
     }
-}
\ No newline at end of file
+
+    async fn handle_monster_killed(player_id: Uuid, expedition: &Expedition, encounter: &Encounter) {
+        let ws_manager = WebSocketManager::global();
+        let monster = &encounter.monster;
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::MonsterKilled,
+            Box::new(MonsterKilled {
+                monster_id: monster.id.clone(),
+                name: monster.name.clone(),
+                exp_reward: monster.exp_reward,
+            }) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_log_to_player(
+            player_id,
+            format!("You defeated the {}!", monster.name),
+        ).await;
+
+        Rewards::grant_exp(player_id, monster.exp_reward).await;
+
+        if rand::random::<f32>() < monster.loot_chance
+            && let Some(loot_item) = LootGenerator::roll(player_id, &expedition.kind, expedition.difficulty) {
+            Rewards::distribute_loot(player_id, vec![loot_item]).await;
+        }
+    }
+}
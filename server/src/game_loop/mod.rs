@@ -1,3 +1,4 @@
+#[allow(clippy::module_inception)]
 mod game_loop;
 
 pub use game_loop::GameLoop;
@@ -1,7 +1,8 @@
 use crate::game_loop::GameLoop;
-use crate::models::{ChatMessage, Expedition, Item, Player, PlayerAttributes, PlayerResource, PlayerState, PlayerStats, Slot};
+use crate::meta::{validate_level_to_exp, ItemCatalog, RecipeBook, ShopCatalog, ITEM_TEMPLATES_PATH, RECIPES_PATH, SHOP_CATALOG_PATH};
+use crate::models::{Buff, Buyback, ChatMessage, DestroyConfirmation, EnchantAttempt, Expedition, Item, ItemAudit, Mail, MarketListing, Party, Player, PlayerAttributes, PlayerResource, PlayerState, PlayerStats, SalvageRecord, ShopStock, Slot, Trade};
 use crate::server::GameServer;
-use crate::store::Store;
+use crate::store::{ensure_schema, Store};
 use std::sync::Arc;
 
 mod models;
@@ -17,7 +18,22 @@ mod services;
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv().ok();
 
+    validate_level_to_exp()?;
+
+    let item_catalog = ItemCatalog::load(ITEM_TEMPLATES_PATH)?;
+    ItemCatalog::initialize_global(item_catalog)
+        .expect("Failed to initialize global ItemCatalog");
+
+    let shop_catalog = ShopCatalog::load(SHOP_CATALOG_PATH)?;
+    ShopCatalog::initialize_global(shop_catalog)
+        .expect("Failed to initialize global ShopCatalog");
+
+    let recipe_book = RecipeBook::load(RECIPES_PATH)?;
+    RecipeBook::initialize_global(recipe_book)
+        .expect("Failed to initialize global RecipeBook");
+
     let db = sled::open("./game_data").map_err(|e| format!("sled open failed: {e}"))?;
+    ensure_schema(&db)?;
 
     let player_store: Store<Player> = Store::with_persistence(
         db.clone(),
@@ -64,6 +80,61 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "expeditions",
     )?;
 
+    let buffs_store: Store<Buff> = Store::with_persistence(
+        db.clone(),
+        "buffs",
+    )?;
+
+    let enchant_attempts_store: Store<EnchantAttempt> = Store::with_persistence(
+        db.clone(),
+        "enchant_attempts",
+    )?;
+
+    let parties_store: Store<Party> = Store::with_persistence(
+        db.clone(),
+        "parties",
+    )?;
+
+    let shop_stock_store: Store<ShopStock> = Store::with_persistence(
+        db.clone(),
+        "shop_stock",
+    )?;
+
+    let buybacks_store: Store<Buyback> = Store::with_persistence(
+        db.clone(),
+        "buybacks",
+    )?;
+
+    let trades_store: Store<Trade> = Store::with_persistence(
+        db.clone(),
+        "trades",
+    )?;
+
+    let market_listings_store: Store<MarketListing> = Store::with_persistence(
+        db.clone(),
+        "market_listings",
+    )?;
+
+    let mail_store: Store<Mail> = Store::with_persistence(
+        db.clone(),
+        "mail",
+    )?;
+
+    let salvage_records_store: Store<SalvageRecord> = Store::with_persistence(
+        db.clone(),
+        "salvage_records",
+    )?;
+
+    let item_audits_store: Store<ItemAudit> = Store::with_persistence(
+        db.clone(),
+        "item_audits",
+    )?;
+
+    let destroy_confirmations_store: Store<DestroyConfirmation> = Store::with_persistence(
+        db.clone(),
+        "destroy_confirmations",
+    )?;
+
     let game_server = Arc::new(GameServer::new(
         player_store,
         player_resource_store,
@@ -74,6 +145,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         slots_store,
         chat_store,
         expeditions_store,
+        buffs_store,
+        enchant_attempts_store,
+        parties_store,
+        shop_stock_store,
+        buybacks_store,
+        trades_store,
+        market_listings_store,
+        mail_store,
+        salvage_records_store,
+        item_audits_store,
+        destroy_confirmations_store,
     ));
 
     GameServer::initialize_global(game_server.clone())
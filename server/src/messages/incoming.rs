@@ -1,5 +1,6 @@
-use crate::models::{ChatKind, SlotKind};
+use crate::models::{ChatKind, EnchantMaterial, ItemKind, ItemTier, PartyLootRule, SlotKind};
 use serde::Deserialize;
+use uuid::Uuid;
 
 #[derive(Debug, Deserialize)]
 pub struct TakeItem {
@@ -13,6 +14,25 @@ pub struct DropItem {
     pub kind: SlotKind,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct UseItem {
+    pub index: u64,
+    pub kind: SlotKind,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EnchantItem {
+    pub index: u64,
+    pub kind: SlotKind,
+    pub material: EnchantMaterial,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpgradeCompass {
+    pub index: u64,
+    pub kind: SlotKind,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SendChatMessage {
     pub kind: ChatKind,
@@ -20,7 +40,218 @@ pub struct SendChatMessage {
     pub content: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AllocateAttributes {
+    #[serde(default)]
+    pub strength: u32,
+    #[serde(default)]
+    pub dexterity: u32,
+    #[serde(default)]
+    pub vitality: u32,
+    #[serde(default)]
+    pub intelligence: u32,
+    #[serde(default)]
+    pub spirit: u32,
+    #[serde(default)]
+    pub luck: u32,
+}
+
+#[allow(dead_code)]
 #[derive(Debug, Deserialize)]
 pub struct StartExpedition {
     // No payload is required to start an expedition
 }
+
+#[derive(Debug, Deserialize)]
+pub struct InviteToParty {
+    pub player_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AcceptPartyInvite {
+    pub party_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeclinePartyInvite {
+    pub party_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct KickFromParty {
+    pub player_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetPartyLootRule {
+    pub loot_rule: PartyLootRule,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExpeditionHistory {
+    #[serde(default)]
+    pub page: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BuyItem {
+    pub template_id: String,
+    #[serde(default = "default_quantity")]
+    pub quantity: u64,
+}
+
+fn default_quantity() -> u64 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SellItem {
+    pub index: u64,
+    pub kind: SlotKind,
+    #[serde(default)]
+    pub quantity: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BuybackItem {
+    pub buyback_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RequestTrade {
+    pub player_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AcceptTrade {
+    pub trade_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddTradeItem {
+    pub index: u64,
+    pub kind: SlotKind,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveTradeItem {
+    pub item_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetTradeCin {
+    pub amount: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListMarketItem {
+    pub index: u64,
+    pub kind: SlotKind,
+    #[serde(default)]
+    pub quantity: Option<u64>,
+    pub price: u64,
+    pub duration_hours: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BuyMarketListing {
+    pub listing_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelMarketListing {
+    pub listing_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchMarket {
+    #[serde(default)]
+    pub kind: Option<ItemKind>,
+    #[serde(default)]
+    pub tier: Option<ItemTier>,
+    #[serde(default)]
+    pub min_level: Option<u32>,
+    #[serde(default)]
+    pub max_level: Option<u32>,
+    #[serde(default)]
+    pub page: usize,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReadMail {
+    pub mail_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClaimAttachments {
+    pub mail_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteMail {
+    pub mail_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Craft {
+    pub recipe_id: String,
+    #[serde(default = "default_quantity")]
+    pub times: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SalvageItem {
+    pub index: u64,
+    #[serde(default = "default_salvage_kind")]
+    pub kind: SlotKind,
+}
+
+fn default_salvage_kind() -> SlotKind {
+    SlotKind::Inventory
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SalvageBelowTier {
+    pub tier: ItemTier,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UndoSalvage {
+    pub salvage_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SplitStack {
+    pub index: u64,
+    pub kind: SlotKind,
+    pub quantity: u64,
+    pub target_index: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MoveItem {
+    pub from_index: u64,
+    pub from_kind: SlotKind,
+    pub to_index: u64,
+    pub to_kind: SlotKind,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum InventorySort {
+    Kind,
+    Tier,
+    Name,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SortInventory {
+    pub by: InventorySort,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DestroyItem {
+    pub index: u64,
+    pub kind: SlotKind,
+    pub quantity: Option<u64>,
+    pub token: Option<Uuid>,
+}
@@ -2,6 +2,7 @@ mod incoming;
 mod outgoing;
 
 pub use incoming::*;
+pub use outgoing::*;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -38,6 +39,46 @@ pub enum IncomingEvent {
     StartExpedition,
     EndExpedition,
     ToggleLoot,
+    AllocateAttributes,
+    UseItem,
+    EnchantItem,
+    UpgradeCompass,
+    InviteToParty,
+    AcceptPartyInvite,
+    DeclinePartyInvite,
+    LeaveParty,
+    KickFromParty,
+    SetPartyLootRule,
+    ExpeditionHistory,
+    BuyItem,
+    SellItem,
+    BuybackItem,
+    RequestTrade,
+    AcceptTrade,
+    AddTradeItem,
+    RemoveTradeItem,
+    SetTradeCin,
+    LockTrade,
+    ConfirmTrade,
+    CancelTrade,
+    ListMarketItem,
+    BuyMarketListing,
+    CancelMarketListing,
+    SearchMarket,
+    ListMail,
+    ReadMail,
+    ClaimAttachments,
+    DeleteMail,
+    Craft,
+    SalvageItem,
+    SalvageBelowTier,
+    UndoSalvage,
+    SplitStack,
+    MoveItem,
+    SortInventory,
+    CompactStacks,
+    BuyStashTab,
+    DestroyItem,
 }
 
 #[derive(Debug, Serialize)]
@@ -48,11 +89,37 @@ pub enum OutgoingEvent {
     PlayerAttributes,
     PlayerState,
     PlayerStats,
+    PlayerBuffs,
+    RuneBreakdown,
+    Party,
+    PartyInvite,
     Slots,
     Meta,
     ChatMessage,
     ExpeditionCountup,
     GainedExperience,
     GainedCin,
+    GainedItem,
+    LevelUp,
+    MonsterEncounter,
+    CombatHit,
+    MonsterKilled,
+    PlayerDefeated,
+    OfflineReport,
+    ExpeditionHistory,
+    ShopListings,
+    ShopBuybacks,
+    Trade,
+    TradeRequest,
+    MarketListings,
+    MarketOwnListings,
+    Mailbox,
+    Mail,
+    CraftResult,
+    SalvageResult,
+    SalvageUndone,
+    StashInfo,
+    DestroyConfirmation,
+    ItemDestroyed,
     Log,
 }
\ No newline at end of file
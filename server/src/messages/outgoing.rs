@@ -1 +1,219 @@
+use crate::meta::{exp_to_next_level, level_progress, Monster};
+use crate::models::{ExpeditionDifficulty, ExpeditionEndReason, ExpeditionKind, Item, MarketListing, PartyLootRule, Player, RuneFamily, RuneModifier, RuneTotals};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
 
+#[derive(Debug, Serialize)]
+pub struct PlayerInfo {
+    #[serde(flatten)]
+    pub player: Player,
+    pub exp_to_next_level: Option<u64>,
+    pub level_progress: f32,
+}
+
+impl PlayerInfo {
+    pub fn new(player: Player) -> Self {
+        Self {
+            exp_to_next_level: exp_to_next_level(player.exp),
+            level_progress: level_progress(player.exp),
+            player,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LevelUp {
+    pub level: u8,
+    pub attribute_points: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonsterEncounter {
+    pub monster: Monster,
+    pub monster_hp: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub enum Combatant {
+    Player,
+    Monster,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CombatHit {
+    pub attacker: Combatant,
+    pub damage: u64,
+    pub monster_hp: u64,
+    pub player_hp: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MonsterKilled {
+    pub monster_id: String,
+    pub name: String,
+    pub exp_reward: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlayerDefeated {
+    pub monster_id: String,
+    pub name: String,
+    pub exp_lost: u64,
+    pub respawn_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RuneContribution {
+    pub index: u64,
+    pub name: String,
+    pub family: RuneFamily,
+    pub modifiers: Vec<RuneModifier>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RuneSetContribution {
+    pub family: RuneFamily,
+    pub equipped: u32,
+    pub pieces: u32,
+    pub active: bool,
+    pub modifiers: Vec<RuneModifier>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RuneBreakdown {
+    pub runes: Vec<RuneContribution>,
+    pub sets: Vec<RuneSetContribution>,
+    pub totals: RuneTotals,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartyMember {
+    pub player_id: Uuid,
+    pub name: String,
+    pub level: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartyInfo {
+    pub id: Uuid,
+    pub leader_id: Uuid,
+    pub loot_rule: PartyLootRule,
+    pub members: Vec<PartyMember>,
+    pub invites: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartyInvite {
+    pub party_id: Uuid,
+    pub leader_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfflineLoot {
+    pub template_id: String,
+    pub name: String,
+    pub quantity: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OfflineReport {
+    pub away_secs: u64,
+    pub simulated_secs: u64,
+    pub exp: u64,
+    pub cin: u64,
+    pub items: Vec<OfflineLoot>,
+    pub mailed_items: Vec<OfflineLoot>,
+    pub energy_spent: u64,
+    pub expedition_ended: bool,
+    pub defeated_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExpeditionHistoryEntry {
+    pub id: Uuid,
+    pub kind: ExpeditionKind,
+    pub difficulty: ExpeditionDifficulty,
+    pub compass_template_id: String,
+    pub compass_name: String,
+    pub party_size: usize,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub duration_secs: u64,
+    pub exp: u64,
+    pub cin: u64,
+    pub items_found: u64,
+    pub energy_spent: u64,
+    pub exp_per_hour: f64,
+    pub cin_per_hour: f64,
+    pub end_reason: Option<ExpeditionEndReason>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExpeditionHistoryPage {
+    pub page: usize,
+    pub total_pages: usize,
+    pub total: usize,
+    pub entries: Vec<ExpeditionHistoryEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ShopItem {
+    pub template_id: String,
+    pub name: String,
+    pub price: u64,
+    pub stock: Option<u64>,
+    pub remaining: Option<u64>,
+    pub restocks_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradeOfferInfo {
+    pub items: Vec<Item>,
+    pub cin: u64,
+    pub locked: bool,
+    pub confirmed: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradeInfo {
+    pub id: Uuid,
+    pub partner_id: Uuid,
+    pub partner_name: String,
+    pub accepted: bool,
+    pub own_offer: TradeOfferInfo,
+    pub partner_offer: TradeOfferInfo,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TradeRequest {
+    pub trade_id: Uuid,
+    pub requester_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarketPage {
+    pub page: usize,
+    pub total_pages: usize,
+    pub total: usize,
+    pub listings: Vec<MarketListing>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CraftResult {
+    pub recipe_id: String,
+    pub recipe_name: String,
+    pub attempts: u64,
+    pub failures: u64,
+    pub upgrades: u64,
+    pub crafted: Vec<Item>,
+    pub mailed: Vec<Item>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StashInfo {
+    pub tabs: u64,
+    pub max_tabs: u64,
+    pub tab_size: u64,
+    pub next_tab_cost: Option<u64>,
+}
@@ -1,22 +1,97 @@
 use serde::{Deserialize, Serialize};
 
 pub const BASE_ATTACK_SPEED: u64 = 3000;
+pub const MIN_ATTACK_SPEED: u64 = 250;
+pub const BASE_HP: u64 = 100;
 pub const BASE_HP_REGENERATION: u64 = 1;
 pub const BASE_HP_REGENERATION_INTERVAL: u64 = 2000;
+pub const BASE_ENERGY_REGENERATION: u64 = 1;
+pub const BASE_ENERGY_REGENERATION_INTERVAL: u64 = 2000;
+pub const ATTRIBUTE_POINTS_PER_LEVEL: u32 = 5;
+pub const MAX_CARRY_PERCENT: u64 = 100;
+pub const MAX_OVERWEIGHT_PERCENT: u64 = 150;
+pub const OVERWEIGHT_ENERGY_FACTOR: f64 = 2.0;
+pub const DEATH_EXP_PENALTY_PERCENT: u64 = 10;
+pub const RESPAWN_DURATION_SECS: i64 = 30;
+pub const RESPAWN_HP_PERCENT: u64 = 25;
+pub const MAX_PARTY_SIZE: usize = 4;
+pub const PARTY_INVITE_SECS: i64 = 60;
+pub const MAX_OFFLINE_SECS: u64 = 28800;
+pub const EXPEDITION_HISTORY_PAGE_SIZE: usize = 10;
+pub const BUYBACK_LIMIT: usize = 10;
+pub const MAX_TRADE_ITEMS: usize = 8;
+pub const MARKET_LISTING_FEE_PERCENT: u64 = 5;
+pub const MAX_MARKET_LISTINGS: usize = 10;
+pub const MAX_MARKET_LISTING_HOURS: u64 = 48;
+pub const MARKET_PAGE_SIZE: usize = 20;
+pub const MAX_CRAFT_BATCH: u64 = 10;
+pub const SALVAGE_UNDO_SECS: i64 = 10;
+pub const STASH_TAB_SIZE: u64 = 28;
+pub const MAX_STASH_TABS: u64 = 5;
+pub const STASH_TAB_BASE_COST: u64 = 5000;
+pub const DESTROY_CONFIRM_SECS: i64 = 30;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct BaseStats {
     pub base_attack_speed: u64,
+    pub min_attack_speed: u64,
+    pub base_hp: u64,
     pub base_hp_regeneration: u64,
     pub base_hp_regen_interval: u64,
+    pub base_energy_regeneration: u64,
+    pub base_energy_regen_interval: u64,
+    pub attribute_points_per_level: u32,
+    pub max_carry_percent: u64,
+    pub max_overweight_percent: u64,
+    pub overweight_energy_factor: f64,
+    pub death_exp_penalty_percent: u64,
+    pub respawn_duration_secs: i64,
+    pub max_party_size: usize,
+    pub party_invite_secs: i64,
+    pub max_offline_secs: u64,
+    pub buyback_limit: usize,
+    pub max_trade_items: usize,
+    pub market_listing_fee_percent: u64,
+    pub max_market_listings: usize,
+    pub max_market_listing_hours: u64,
+    pub max_craft_batch: u64,
+    pub salvage_undo_secs: i64,
+    pub stash_tab_size: u64,
+    pub max_stash_tabs: u64,
+    pub stash_tab_base_cost: u64,
+    pub destroy_confirm_secs: i64,
 }
 
 impl BaseStats {
     pub fn new() -> Self {
         Self {
             base_attack_speed: BASE_ATTACK_SPEED,
+            min_attack_speed: MIN_ATTACK_SPEED,
+            base_hp: BASE_HP,
             base_hp_regeneration: BASE_HP_REGENERATION,
             base_hp_regen_interval: BASE_HP_REGENERATION_INTERVAL,
+            base_energy_regeneration: BASE_ENERGY_REGENERATION,
+            base_energy_regen_interval: BASE_ENERGY_REGENERATION_INTERVAL,
+            attribute_points_per_level: ATTRIBUTE_POINTS_PER_LEVEL,
+            max_carry_percent: MAX_CARRY_PERCENT,
+            max_overweight_percent: MAX_OVERWEIGHT_PERCENT,
+            overweight_energy_factor: OVERWEIGHT_ENERGY_FACTOR,
+            death_exp_penalty_percent: DEATH_EXP_PENALTY_PERCENT,
+            respawn_duration_secs: RESPAWN_DURATION_SECS,
+            max_party_size: MAX_PARTY_SIZE,
+            party_invite_secs: PARTY_INVITE_SECS,
+            max_offline_secs: MAX_OFFLINE_SECS,
+            buyback_limit: BUYBACK_LIMIT,
+            max_trade_items: MAX_TRADE_ITEMS,
+            market_listing_fee_percent: MARKET_LISTING_FEE_PERCENT,
+            max_market_listings: MAX_MARKET_LISTINGS,
+            max_market_listing_hours: MAX_MARKET_LISTING_HOURS,
+            max_craft_batch: MAX_CRAFT_BATCH,
+            salvage_undo_secs: SALVAGE_UNDO_SECS,
+            stash_tab_size: STASH_TAB_SIZE,
+            max_stash_tabs: MAX_STASH_TABS,
+            stash_tab_base_cost: STASH_TAB_BASE_COST,
+            destroy_confirm_secs: DESTROY_CONFIRM_SECS,
         }
     }
-}
\ No newline at end of file
+}
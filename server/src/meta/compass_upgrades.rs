@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CompassUpgrade {
+    pub from_template_id: String,
+    pub to_template_id: String,
+    pub cin_cost: u64,
+    pub materials: Vec<(String, u64)>,
+}
+
+impl CompassUpgrade {
+    fn new(from_template_id: &str, to_template_id: &str, cin_cost: u64, materials: &[(&str, u64)]) -> Self {
+        Self {
+            from_template_id: from_template_id.to_string(),
+            to_template_id: to_template_id.to_string(),
+            cin_cost,
+            materials: materials
+                .iter()
+                .map(|(template_id, quantity)| (template_id.to_string(), *quantity))
+                .collect(),
+        }
+    }
+}
+
+pub fn compass_upgrades() -> Vec<CompassUpgrade> {
+    vec![
+        CompassUpgrade::new("hunter_compass", "seasoned_hunter_compass", 250, &[("wolf_pelt", 10)]),
+        CompassUpgrade::new("seasoned_hunter_compass", "veteran_hunter_compass", 1000, &[("wolf_pelt", 20), ("boar_tusk", 10)]),
+        CompassUpgrade::new("veteran_hunter_compass", "master_hunter_compass", 4000, &[("boar_tusk", 20), ("bear_claw", 10)]),
+    ]
+}
+
+pub fn compass_upgrade(from_template_id: &str) -> Option<CompassUpgrade> {
+    compass_upgrades()
+        .into_iter()
+        .find(|upgrade| upgrade.from_template_id == from_template_id)
+}
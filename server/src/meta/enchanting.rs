@@ -0,0 +1,31 @@
+use crate::models::EnchantOutcome;
+
+pub const MAX_ENCHANT_LEVEL: u32 = 10;
+pub const ENCHANT_SCROLL_TEMPLATE_ID: &str = "enchant_scroll";
+
+const ENCHANT_SUCCESS_CHANCES: [f32; MAX_ENCHANT_LEVEL as usize] = [0.95, 0.9, 0.8, 0.7, 0.6, 0.5, 0.4, 0.3, 0.2, 0.1];
+
+pub fn enchant_success_chance(current_level: u32) -> f32 {
+    ENCHANT_SUCCESS_CHANCES
+        .get(current_level as usize)
+        .copied()
+        .unwrap_or(0.0)
+}
+
+pub fn enchant_cin_cost(current_level: u32) -> u64 {
+    let next_level = current_level as u64 + 1;
+
+    50 * next_level * next_level
+}
+
+pub fn enchant_failure_weights(current_level: u32) -> Vec<(EnchantOutcome, u32)> {
+    match current_level {
+        0..=2 => vec![(EnchantOutcome::Failed, 100)],
+        3..=5 => vec![(EnchantOutcome::Failed, 70), (EnchantOutcome::Downgraded, 30)],
+        _ => vec![
+            (EnchantOutcome::Failed, 50),
+            (EnchantOutcome::Downgraded, 35),
+            (EnchantOutcome::Destroyed, 15),
+        ],
+    }
+}
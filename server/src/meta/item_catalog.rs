@@ -0,0 +1,50 @@
+use crate::meta::ItemTemplate;
+use once_cell::sync::OnceCell;
+use std::collections::BTreeMap;
+
+pub const ITEM_TEMPLATES_PATH: &str = "./data/item_templates.json";
+
+#[derive(Debug, Clone)]
+pub struct ItemCatalog {
+    templates: BTreeMap<String, ItemTemplate>,
+}
+
+static ITEM_CATALOG: OnceCell<ItemCatalog> = OnceCell::new();
+
+impl ItemCatalog {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read item templates from {}: {}", path, e))?;
+
+        let entries: Vec<ItemTemplate> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse item templates from {}: {}", path, e))?;
+
+        let mut templates = BTreeMap::new();
+
+        for template in entries {
+            if templates.contains_key(&template.id) {
+                return Err(format!("Duplicate item template id '{}'", template.id));
+            }
+
+            templates.insert(template.id.clone(), template);
+        }
+
+        Ok(Self { templates })
+    }
+
+    pub fn initialize_global(catalog: ItemCatalog) -> Result<(), &'static str> {
+        ITEM_CATALOG.set(catalog).map_err(|_| "ItemCatalog already initialized")
+    }
+
+    pub fn global() -> &'static ItemCatalog {
+        ITEM_CATALOG.get().expect("ItemCatalog not initialized")
+    }
+
+    pub fn get(&self, template_id: &str) -> Option<&ItemTemplate> {
+        self.templates.get(template_id)
+    }
+
+    pub fn templates(&self) -> &BTreeMap<String, ItemTemplate> {
+        &self.templates
+    }
+}
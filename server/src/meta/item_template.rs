@@ -0,0 +1,46 @@
+use crate::models::{Item, ItemEffect, ItemKind, ItemRequirements, ItemRune, ItemStats, ItemTier};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemTemplate {
+    pub id: String,
+    pub kind: ItemKind,
+    pub name: String,
+    pub tier: ItemTier,
+    pub icon: String,
+    pub level: u32,
+    pub description: String,
+    pub weight: f32,
+    pub is_stackable: bool,
+    pub is_usable: bool,
+    pub stats: Option<ItemStats>,
+    pub requirements: Option<ItemRequirements>,
+    pub effect: Option<ItemEffect>,
+    pub rune: Option<ItemRune>,
+}
+
+impl ItemTemplate {
+    pub fn create(&self, player_id: Uuid, quantity: u64) -> Item {
+        Item {
+            id: Uuid::new_v4(),
+            player_id,
+            template_id: self.id.clone(),
+            kind: self.kind.clone(),
+            name: self.name.clone(),
+            tier: self.tier.clone(),
+            icon: self.icon.clone(),
+            quantity,
+            level: self.level,
+            enchanted: 0,
+            description: self.description.clone(),
+            weight: self.weight,
+            is_stackable: self.is_stackable,
+            is_usable: self.is_usable,
+            stats: self.stats.clone(),
+            requirements: self.requirements.clone(),
+            effect: self.effect.clone(),
+            rune: self.rune.clone(),
+        }
+    }
+}
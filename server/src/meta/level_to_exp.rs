@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-pub fn level_to_exp() -> HashMap<u8, u64> {
-    HashMap::from([
+pub fn level_to_exp() -> BTreeMap<u8, u64> {
+    BTreeMap::from([
         (2u8, 1200),
         (3u8, 12000),
         (4u8, 17000),
@@ -63,3 +63,108 @@ pub fn level_to_exp() -> HashMap<u8, u64> {
         (60u8, 1891000),
     ])
 }
+
+pub fn validate_level_to_exp() -> Result<(), String> {
+    validate_curve(&level_to_exp())
+}
+
+fn validate_curve(curve: &BTreeMap<u8, u64>) -> Result<(), String> {
+    let mut previous_exp = 0u64;
+
+    for (expected_level, (&level, &required_exp)) in (2u8..).zip(curve) {
+        if level != expected_level {
+            return Err(format!("Level curve is missing level {}", expected_level));
+        }
+
+        if required_exp <= previous_exp {
+            return Err(format!("Level {} requires {} exp, which is not above the previous level", level, required_exp));
+        }
+
+        previous_exp = required_exp;
+    }
+
+    Ok(())
+}
+
+pub fn exp_for_level(level: u8) -> u64 {
+    level_to_exp().get(&level).copied().unwrap_or(0)
+}
+
+pub fn level_for_exp(exp: u64) -> u8 {
+    level_to_exp()
+        .iter()
+        .rev()
+        .find(|(_, required_exp)| exp >= **required_exp)
+        .map(|(level, _)| *level)
+        .unwrap_or(1)
+}
+
+pub fn exp_to_next_level(exp: u64) -> Option<u64> {
+    let level = level_for_exp(exp);
+
+    level_to_exp()
+        .get(&(level + 1))
+        .map(|required_exp| required_exp - exp)
+}
+
+pub fn level_progress(exp: u64) -> f32 {
+    let level = level_for_exp(exp);
+    let current_exp = exp_for_level(level);
+
+    match level_to_exp().get(&(level + 1)) {
+        Some(next_exp) => {
+            let span = next_exp - current_exp;
+            ((exp - current_exp) as f32 / span as f32 * 100.0).clamp(0.0, 100.0)
+        }
+        None => 100.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_curve_is_valid() {
+        assert_eq!(validate_level_to_exp(), Ok(()));
+    }
+
+    #[test]
+    fn validate_rejects_gaps_and_non_increasing_exp() {
+        let missing = BTreeMap::from([(2u8, 100), (4u8, 300)]);
+        assert_eq!(validate_curve(&missing), Err("Level curve is missing level 3".to_string()));
+
+        let flat = BTreeMap::from([(2u8, 100), (3u8, 100)]);
+        assert!(validate_curve(&flat).is_err());
+
+        let zero = BTreeMap::from([(2u8, 0)]);
+        assert!(validate_curve(&zero).is_err());
+    }
+
+    #[test]
+    fn exp_for_level_reads_the_curve() {
+        assert_eq!(exp_for_level(1), 0);
+        assert_eq!(exp_for_level(2), 1200);
+        assert_eq!(exp_for_level(60), 1891000);
+        assert_eq!(exp_for_level(61), 0);
+    }
+
+    #[test]
+    fn level_for_exp_uses_thresholds() {
+        assert_eq!(level_for_exp(0), 1);
+        assert_eq!(level_for_exp(1199), 1);
+        assert_eq!(level_for_exp(1200), 2);
+        assert_eq!(level_for_exp(11999), 2);
+        assert_eq!(level_for_exp(12000), 3);
+        assert_eq!(level_for_exp(u64::MAX), 60);
+    }
+
+    #[test]
+    fn level_for_exp_round_trips_every_level() {
+        for (&level, &required_exp) in &level_to_exp() {
+            assert_eq!(level_for_exp(required_exp), level);
+            assert_eq!(level_for_exp(required_exp - 1), level - 1);
+            assert_eq!(exp_for_level(level_for_exp(required_exp)), required_exp);
+        }
+    }
+}
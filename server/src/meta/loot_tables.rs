@@ -0,0 +1,180 @@
+use crate::models::{ExpeditionDifficulty, ExpeditionKind, ItemTier};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LootTable {
+    pub tier_weights: Vec<(ItemTier, u32)>,
+    pub entries: Vec<LootEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LootEntry {
+    pub template_id: String,
+    pub weight: u32,
+    pub min_quantity: u64,
+    pub max_quantity: u64,
+}
+
+impl LootEntry {
+    fn new(template_id: &str, weight: u32, min_quantity: u64, max_quantity: u64) -> Self {
+        Self {
+            template_id: template_id.to_string(),
+            weight,
+            min_quantity,
+            max_quantity,
+        }
+    }
+}
+
+pub fn loot_table(kind: &ExpeditionKind, difficulty: ExpeditionDifficulty) -> LootTable {
+    match kind {
+        ExpeditionKind::Hunt => hunt_loot_table(difficulty),
+        ExpeditionKind::Gathering => LootTable {
+            tier_weights: tier_weights(difficulty),
+            entries: vec![
+                LootEntry::new("herb_bundle", 50, 1, 3),
+                LootEntry::new("wild_berries", 40, 1, 4),
+                LootEntry::new("moonpetal", 20, 1, 2),
+                LootEntry::new("sunroot", 8, 1, 1),
+            ],
+        },
+        ExpeditionKind::Mining => LootTable {
+            tier_weights: tier_weights(difficulty),
+            entries: vec![
+                LootEntry::new("copper_ore", 50, 1, 3),
+                LootEntry::new("iron_ore", 25, 1, 2),
+                LootEntry::new("silver_ore", 10, 1, 1),
+                LootEntry::new("raw_gem", 3, 1, 1),
+            ],
+        },
+        ExpeditionKind::Fishing => LootTable {
+            tier_weights: tier_weights(difficulty),
+            entries: vec![
+                LootEntry::new("river_trout", 50, 1, 2),
+                LootEntry::new("silver_carp", 25, 1, 1),
+                LootEntry::new("golden_eel", 8, 1, 1),
+                LootEntry::new("pearl", 3, 1, 1),
+            ],
+        },
+    }
+}
+
+pub fn rare_find_table(kind: &ExpeditionKind) -> LootTable {
+    let tier_weights = vec![
+        (ItemTier::Rare, 70),
+        (ItemTier::Epic, 25),
+        (ItemTier::Legendary, 5),
+    ];
+
+    let entries = match kind {
+        ExpeditionKind::Hunt => vec![],
+        ExpeditionKind::Gathering => vec![
+            LootEntry::new("lucky_clover", 10, 1, 1),
+            LootEntry::new("ancient_seed", 10, 1, 1),
+            LootEntry::new("heart_of_the_wild", 1, 1, 1),
+        ],
+        ExpeditionKind::Mining => vec![
+            LootEntry::new("star_rune", 10, 1, 1),
+            LootEntry::new("lucky_star_rune", 5, 1, 1),
+            LootEntry::new("raw_gem", 10, 1, 2),
+        ],
+        ExpeditionKind::Fishing => vec![
+            LootEntry::new("golden_eel", 10, 1, 1),
+            LootEntry::new("pearl", 10, 1, 2),
+            LootEntry::new("sunken_relic", 1, 1, 1),
+        ],
+    };
+
+    LootTable {
+        tier_weights,
+        entries,
+    }
+}
+
+fn tier_weights(difficulty: ExpeditionDifficulty) -> Vec<(ItemTier, u32)> {
+    match difficulty {
+        ExpeditionDifficulty::Novice => vec![
+            (ItemTier::Common, 700),
+            (ItemTier::Uncommon, 220),
+            (ItemTier::Rare, 60),
+            (ItemTier::Epic, 15),
+            (ItemTier::Legendary, 5),
+        ],
+        ExpeditionDifficulty::Adept => vec![
+            (ItemTier::Common, 550),
+            (ItemTier::Uncommon, 300),
+            (ItemTier::Rare, 110),
+            (ItemTier::Epic, 30),
+            (ItemTier::Legendary, 10),
+        ],
+        ExpeditionDifficulty::Veteran => vec![
+            (ItemTier::Common, 400),
+            (ItemTier::Uncommon, 340),
+            (ItemTier::Rare, 180),
+            (ItemTier::Epic, 60),
+            (ItemTier::Legendary, 20),
+        ],
+        ExpeditionDifficulty::Elite => vec![
+            (ItemTier::Common, 250),
+            (ItemTier::Uncommon, 350),
+            (ItemTier::Rare, 250),
+            (ItemTier::Epic, 110),
+            (ItemTier::Legendary, 40),
+        ],
+    }
+}
+
+fn hunt_loot_table(difficulty: ExpeditionDifficulty) -> LootTable {
+    let mut entries = hunt_entries();
+
+    match difficulty {
+        ExpeditionDifficulty::Novice => {}
+        ExpeditionDifficulty::Adept => entries.extend([
+            LootEntry::new("boar_tusk", 30, 1, 2),
+        ]),
+        ExpeditionDifficulty::Veteran => entries.extend([
+            LootEntry::new("boar_tusk", 30, 1, 3),
+            LootEntry::new("bear_claw", 25, 1, 2),
+        ]),
+        ExpeditionDifficulty::Elite => entries.extend([
+            LootEntry::new("boar_tusk", 30, 2, 3),
+            LootEntry::new("bear_claw", 25, 1, 3),
+            LootEntry::new("stag_antler", 15, 1, 2),
+        ]),
+    }
+
+    LootTable {
+        tier_weights: tier_weights(difficulty),
+        entries,
+    }
+}
+
+fn hunt_entries() -> Vec<LootEntry> {
+    vec![
+        LootEntry::new("wolf_pelt", 40, 1, 3),
+        LootEntry::new("minor_energy_tonic", 40, 1, 3),
+        LootEntry::new("healing_draught", 30, 1, 2),
+        LootEntry::new("hunters_brew", 15, 1, 1),
+        LootEntry::new("scholars_tea", 15, 1, 1),
+        LootEntry::new("lucky_clover", 5, 1, 1),
+        LootEntry::new("enchant_scroll", 20, 1, 2),
+        LootEntry::new("leather_cap", 20, 1, 1),
+        LootEntry::new("worn_gloves", 20, 1, 1),
+        LootEntry::new("hide_vest", 30, 1, 1),
+        LootEntry::new("tracker_boots", 30, 1, 1),
+        LootEntry::new("copper_band", 10, 1, 1),
+        LootEntry::new("hunters_cloak", 10, 1, 1),
+        LootEntry::new("fang_necklace", 5, 1, 1),
+        LootEntry::new("heart_of_the_wild", 1, 1, 1),
+        LootEntry::new("fang_rune", 15, 1, 1),
+        LootEntry::new("bark_rune", 15, 1, 1),
+        LootEntry::new("stone_rune", 15, 1, 1),
+        LootEntry::new("greater_fang_rune", 8, 1, 1),
+        LootEntry::new("greater_bark_rune", 8, 1, 1),
+        LootEntry::new("star_rune", 8, 1, 1),
+        LootEntry::new("lucky_star_rune", 3, 1, 1),
+        LootEntry::new("herbalist_compass", 4, 1, 1),
+        LootEntry::new("prospector_compass", 4, 1, 1),
+        LootEntry::new("angler_compass", 4, 1, 1),
+    ]
+}
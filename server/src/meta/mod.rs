@@ -1,15 +1,38 @@
 mod level_to_exp;
 mod base_stats;
+mod item_template;
+mod item_catalog;
+mod loot_tables;
+mod monsters;
+mod rune_sets;
+mod enchanting;
+mod compass_upgrades;
+mod shop_catalog;
+mod recipes;
+mod salvage;
 
 pub use base_stats::*;
-pub use level_to_exp::level_to_exp;
+pub use compass_upgrades::{compass_upgrade, compass_upgrades, CompassUpgrade};
+pub use enchanting::{enchant_cin_cost, enchant_failure_weights, enchant_success_chance, ENCHANT_SCROLL_TEMPLATE_ID, MAX_ENCHANT_LEVEL};
+pub use item_catalog::{ItemCatalog, ITEM_TEMPLATES_PATH};
+pub use item_template::ItemTemplate;
+pub use level_to_exp::{exp_for_level, exp_to_next_level, level_for_exp, level_progress, level_to_exp, validate_level_to_exp};
+pub use loot_tables::{loot_table, rare_find_table, LootEntry, LootTable};
+pub use monsters::{monsters, Monster};
+pub use recipes::{Recipe, RecipeBook, RECIPES_PATH};
+pub use rune_sets::rune_set_bonuses;
+pub use salvage::{is_salvageable, salvage_yield};
+pub use shop_catalog::{sell_price, ShopCatalog, ShopListing, SHOP_CATALOG_PATH};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Meta {
-    pub level_to_exp: HashMap<u8, u64>,
+    pub level_to_exp: BTreeMap<u8, u64>,
     pub base_stats: BaseStats,
+    pub item_templates: BTreeMap<String, ItemTemplate>,
+    pub compass_upgrades: Vec<CompassUpgrade>,
+    pub recipes: BTreeMap<String, Recipe>,
 }
 
 impl Meta {
@@ -17,6 +40,9 @@ impl Meta {
         Self {
             level_to_exp: level_to_exp(),
             base_stats: BaseStats::new(),
+            item_templates: ItemCatalog::global().templates().clone(),
+            compass_upgrades: compass_upgrades(),
+            recipes: RecipeBook::global().recipes().clone(),
         }
     }
-}
\ No newline at end of file
+}
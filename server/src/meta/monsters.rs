@@ -0,0 +1,61 @@
+use crate::models::ExpeditionKind;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Monster {
+    pub id: String,
+    pub name: String,
+    pub icon: String,
+    pub level: u8,
+    pub hp: u64,
+    pub attack: u64,
+    pub defense: u64,
+    pub attack_speed: u64,
+    pub exp_reward: u64,
+    pub loot_chance: f32,
+    pub spawn_weight: u32,
+}
+
+impl Monster {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        id: &str,
+        name: &str,
+        icon: &str,
+        level: u8,
+        hp: u64,
+        attack: u64,
+        defense: u64,
+        attack_speed: u64,
+        exp_reward: u64,
+        loot_chance: f32,
+        spawn_weight: u32,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            icon: icon.to_string(),
+            level,
+            hp,
+            attack,
+            defense,
+            attack_speed,
+            exp_reward,
+            loot_chance,
+            spawn_weight,
+        }
+    }
+}
+
+pub fn monsters(kind: &ExpeditionKind) -> Vec<Monster> {
+    match kind {
+        ExpeditionKind::Hunt => vec![
+            Monster::new("forest_rat", "Forest Rat", "game-icons:rat", 1, 120, 18, 5, 2500, 6, 0.05, 50),
+            Monster::new("grey_wolf", "Grey Wolf", "game-icons:wolf-head", 3, 260, 32, 12, 2800, 14, 0.1, 30),
+            Monster::new("wild_boar", "Wild Boar", "game-icons:boar", 5, 420, 40, 25, 3200, 24, 0.15, 15),
+            Monster::new("dire_bear", "Dire Bear", "game-icons:bear-head", 10, 900, 70, 40, 3600, 60, 0.3, 4),
+            Monster::new("ancient_stag", "Ancient Stag", "game-icons:deer-head", 15, 1600, 90, 60, 3000, 120, 0.5, 1),
+        ],
+        ExpeditionKind::Gathering | ExpeditionKind::Mining | ExpeditionKind::Fishing => vec![],
+    }
+}
@@ -0,0 +1,92 @@
+use crate::meta::ItemCatalog;
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const RECIPES_PATH: &str = "./data/recipes.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RecipeItem {
+    pub template_id: String,
+    pub quantity: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Recipe {
+    pub id: String,
+    pub name: String,
+    pub inputs: Vec<RecipeItem>,
+    #[serde(default)]
+    pub cin_cost: u64,
+    pub output: RecipeItem,
+    pub success_chance: f32,
+    #[serde(default)]
+    pub upgrade: Option<RecipeItem>,
+    #[serde(default)]
+    pub upgrade_chance: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecipeBook {
+    recipes: BTreeMap<String, Recipe>,
+}
+
+static RECIPE_BOOK: OnceCell<RecipeBook> = OnceCell::new();
+
+impl RecipeBook {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read recipes from {}: {}", path, e))?;
+
+        let entries: Vec<Recipe> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse recipes from {}: {}", path, e))?;
+
+        let item_catalog = ItemCatalog::global();
+        let mut recipes = BTreeMap::new();
+
+        for recipe in entries {
+            let referenced = recipe.inputs
+                .iter()
+                .chain(std::iter::once(&recipe.output))
+                .chain(recipe.upgrade.iter());
+
+            for item in referenced {
+                if item_catalog.get(&item.template_id).is_none() {
+                    return Err(format!("Recipe '{}' references unknown item template '{}'", recipe.id, item.template_id));
+                }
+
+                if item.quantity == 0 {
+                    return Err(format!("Recipe '{}' has a zero quantity for '{}'", recipe.id, item.template_id));
+                }
+            }
+
+            if recipe.inputs.is_empty() {
+                return Err(format!("Recipe '{}' has no inputs", recipe.id));
+            }
+
+            if recipes.contains_key(&recipe.id) {
+                return Err(format!("Duplicate recipe id '{}'", recipe.id));
+            }
+
+            recipes.insert(recipe.id.clone(), recipe);
+        }
+
+        Ok(Self { recipes })
+    }
+
+    pub fn initialize_global(book: RecipeBook) -> Result<(), &'static str> {
+        RECIPE_BOOK.set(book).map_err(|_| "RecipeBook already initialized")
+    }
+
+    pub fn global() -> &'static RecipeBook {
+        RECIPE_BOOK.get().expect("RecipeBook not initialized")
+    }
+
+    pub fn get(&self, recipe_id: &str) -> Option<&Recipe> {
+        self.recipes.get(recipe_id)
+    }
+
+    pub fn recipes(&self) -> &BTreeMap<String, Recipe> {
+        &self.recipes
+    }
+}
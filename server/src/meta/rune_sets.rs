@@ -0,0 +1,42 @@
+use crate::models::{RuneFamily, RuneModifier};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuneSetBonus {
+    pub family: RuneFamily,
+    pub pieces: u32,
+    pub modifiers: Vec<RuneModifier>,
+}
+
+impl RuneSetBonus {
+    fn new(family: RuneFamily, pieces: u32, modifiers: Vec<RuneModifier>) -> Self {
+        Self {
+            family,
+            pieces,
+            modifiers,
+        }
+    }
+}
+
+pub fn rune_set_bonuses(family: RuneFamily) -> Vec<RuneSetBonus> {
+    match family {
+        RuneFamily::Fang => vec![
+            RuneSetBonus::new(family, 2, vec![RuneModifier::Attack(10)]),
+            RuneSetBonus::new(family, 4, vec![RuneModifier::AttackPercent(0.05)]),
+            RuneSetBonus::new(family, 6, vec![RuneModifier::AttackPercent(0.10)]),
+        ],
+        RuneFamily::Bark => vec![
+            RuneSetBonus::new(family, 2, vec![RuneModifier::Defense(10)]),
+            RuneSetBonus::new(family, 4, vec![RuneModifier::DefensePercent(0.05)]),
+            RuneSetBonus::new(family, 6, vec![RuneModifier::DefensePercent(0.10)]),
+        ],
+        RuneFamily::Stone => vec![
+            RuneSetBonus::new(family, 2, vec![RuneModifier::EnergyCostReduction(0.05)]),
+            RuneSetBonus::new(family, 4, vec![RuneModifier::EnergyCostReduction(0.10)]),
+        ],
+        RuneFamily::Star => vec![
+            RuneSetBonus::new(family, 2, vec![RuneModifier::ExpChance(0.01)]),
+            RuneSetBonus::new(family, 4, vec![RuneModifier::LootChance(0.01), RuneModifier::CinChance(0.01)]),
+        ],
+    }
+}
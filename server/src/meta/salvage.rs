@@ -0,0 +1,43 @@
+use crate::meta::ENCHANT_SCROLL_TEMPLATE_ID;
+use crate::models::{Item, ItemKind, ItemTier};
+
+const LEATHER_MATERIALS: [&str; 5] = ["wolf_pelt", "boar_tusk", "bear_claw", "stag_antler", "stag_antler"];
+const METAL_MATERIALS: [&str; 5] = ["copper_ore", "iron_ore", "silver_ore", "raw_gem", "raw_gem"];
+
+fn tier_index(tier: &ItemTier) -> usize {
+    match tier {
+        ItemTier::Common => 0,
+        ItemTier::Uncommon => 1,
+        ItemTier::Rare => 2,
+        ItemTier::Epic => 3,
+        ItemTier::Legendary => 4,
+    }
+}
+
+pub fn is_salvageable(kind: &ItemKind) -> bool {
+    kind.is_enchantable() && *kind != ItemKind::Compass
+}
+
+pub fn salvage_yield(item: &Item) -> Vec<(String, u64)> {
+    let materials = match item.kind {
+        ItemKind::Weapon | ItemKind::Ring | ItemKind::Earring | ItemKind::Necklace | ItemKind::Pendant => METAL_MATERIALS,
+        _ => LEATHER_MATERIALS,
+    };
+
+    let tier = tier_index(&item.tier);
+
+    let mut output = vec![(
+        materials[tier].to_string(),
+        1 + item.level as u64 / 3 + item.enchanted as u64,
+    )];
+
+    if tier >= 2 {
+        output.push((materials[tier - 1].to_string(), 2));
+    }
+
+    if item.enchanted >= 2 {
+        output.push((ENCHANT_SCROLL_TEMPLATE_ID.to_string(), item.enchanted as u64 / 2));
+    }
+
+    output
+}
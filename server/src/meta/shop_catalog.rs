@@ -0,0 +1,97 @@
+use crate::meta::ItemCatalog;
+use crate::models::{Item, ItemKind, ItemTier};
+use once_cell::sync::OnceCell;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub const SHOP_CATALOG_PATH: &str = "./data/shop.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShopListing {
+    pub template_id: String,
+    pub price: u64,
+    #[serde(default)]
+    pub stock: Option<u64>,
+    #[serde(default)]
+    pub restock_secs: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ShopCatalog {
+    listings: BTreeMap<String, ShopListing>,
+}
+
+static SHOP_CATALOG: OnceCell<ShopCatalog> = OnceCell::new();
+
+impl ShopCatalog {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read shop catalog from {}: {}", path, e))?;
+
+        let entries: Vec<ShopListing> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse shop catalog from {}: {}", path, e))?;
+
+        let item_catalog = ItemCatalog::global();
+        let mut listings = BTreeMap::new();
+
+        for listing in entries {
+            if item_catalog.get(&listing.template_id).is_none() {
+                return Err(format!("Shop listing references unknown item template '{}'", listing.template_id));
+            }
+
+            if listing.stock.is_some() != listing.restock_secs.is_some() {
+                return Err(format!("Shop listing '{}' must set both stock and restock_secs", listing.template_id));
+            }
+
+            if listings.contains_key(&listing.template_id) {
+                return Err(format!("Duplicate shop listing '{}'", listing.template_id));
+            }
+
+            listings.insert(listing.template_id.clone(), listing);
+        }
+
+        Ok(Self { listings })
+    }
+
+    pub fn initialize_global(catalog: ShopCatalog) -> Result<(), &'static str> {
+        SHOP_CATALOG.set(catalog).map_err(|_| "ShopCatalog already initialized")
+    }
+
+    pub fn global() -> &'static ShopCatalog {
+        SHOP_CATALOG.get().expect("ShopCatalog not initialized")
+    }
+
+    pub fn get(&self, template_id: &str) -> Option<&ShopListing> {
+        self.listings.get(template_id)
+    }
+
+    pub fn listings(&self) -> impl Iterator<Item = &ShopListing> {
+        self.listings.values()
+    }
+}
+
+fn tier_base_price(tier: &ItemTier) -> u64 {
+    match tier {
+        ItemTier::Common => 2,
+        ItemTier::Uncommon => 10,
+        ItemTier::Rare => 40,
+        ItemTier::Epic => 150,
+        ItemTier::Legendary => 500,
+    }
+}
+
+pub fn sell_price(item: &Item) -> Option<u64> {
+    if item.kind == ItemKind::Currency {
+        return None;
+    }
+
+    let unit_price = tier_base_price(&item.tier) * (item.level as u64 + 1)
+        + 25 * (item.enchanted as u64).pow(2);
+
+    let unit_price = match ShopCatalog::global().get(&item.template_id) {
+        Some(listing) if item.enchanted == 0 => unit_price.min(listing.price / 2),
+        _ => unit_price,
+    };
+
+    Some(unit_price.max(1) * item.quantity)
+}
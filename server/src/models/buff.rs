@@ -0,0 +1,51 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Buff {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub kind: BuffKind,
+    pub value: f32,
+    pub source: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Buff {
+    pub fn new(player_id: Uuid, kind: BuffKind, value: f32, source: String, duration_secs: u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            kind,
+            value,
+            source,
+            expires_at: Utc::now() + Duration::seconds(duration_secs as i64),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+impl super::Model for Buff {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum BuffKind {
+    Attack,
+    AttackSpeed,
+    Defense,
+    ExpChance,
+    LootChance,
+}
+
+impl BuffKind {
+    pub fn affects_stats(&self) -> bool {
+        matches!(self, BuffKind::Attack | BuffKind::AttackSpeed | BuffKind::Defense)
+    }
+}
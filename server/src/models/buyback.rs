@@ -0,0 +1,31 @@
+use crate::models::Item;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Buyback {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub item: Item,
+    pub price: u64,
+    pub sold_at: DateTime<Utc>,
+}
+
+impl Buyback {
+    pub fn new(player_id: Uuid, item: Item, price: u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            item,
+            price,
+            sold_at: Utc::now(),
+        }
+    }
+}
+
+impl super::Model for Buyback {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
@@ -17,6 +17,7 @@ pub enum ChatKind {
     General,
     Trade,
     Whisper,
+    Party,
 }
 
 impl ChatMessage {
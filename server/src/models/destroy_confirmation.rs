@@ -0,0 +1,36 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DestroyConfirmation {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub item_id: Uuid,
+    pub item_name: String,
+    pub quantity: u64,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DestroyConfirmation {
+    pub fn new(player_id: Uuid, item_id: Uuid, item_name: String, quantity: u64, confirm_secs: i64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            item_id,
+            item_name,
+            quantity,
+            expires_at: Utc::now() + Duration::seconds(confirm_secs),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+impl super::Model for DestroyConfirmation {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EnchantAttempt {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub item_id: Uuid,
+    pub template_id: String,
+    pub item_name: String,
+    pub material: EnchantMaterial,
+    pub cost: u64,
+    pub success_chance: f32,
+    pub from_level: u32,
+    pub to_level: Option<u32>,
+    pub outcome: EnchantOutcome,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EnchantAttempt {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        player_id: Uuid,
+        item_id: Uuid,
+        template_id: String,
+        item_name: String,
+        material: EnchantMaterial,
+        cost: u64,
+        success_chance: f32,
+        from_level: u32,
+        to_level: Option<u32>,
+        outcome: EnchantOutcome,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            item_id,
+            template_id,
+            item_name,
+            material,
+            cost,
+            success_chance,
+            from_level,
+            to_level,
+            outcome,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+impl super::Model for EnchantAttempt {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum EnchantMaterial {
+    Scroll,
+    Cin,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum EnchantOutcome {
+    Success,
+    Failed,
+    Downgraded,
+    Destroyed,
+}
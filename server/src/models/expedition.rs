@@ -7,20 +7,41 @@ pub struct Expedition {
     pub id: Uuid,
     pub participants: Vec<Uuid>,
     pub kind: ExpeditionKind,
+    pub difficulty: ExpeditionDifficulty,
+    pub compass_template_id: String,
+    pub summaries: Vec<ExpeditionSummary>,
     pub started_at: DateTime<Utc>,
     pub ended_at: Option<DateTime<Utc>>,
 }
 
 impl Expedition {
-    pub fn new(participants: Vec<Uuid>, kind: ExpeditionKind) -> Self {
+    pub fn new(
+        participants: Vec<Uuid>,
+        kind: ExpeditionKind,
+        difficulty: ExpeditionDifficulty,
+        compass_template_id: String,
+    ) -> Self {
+        let summaries = participants.iter().map(|id| ExpeditionSummary::new(*id)).collect();
+
         Self {
             id: Uuid::new_v4(),
             participants,
             kind,
+            difficulty,
+            compass_template_id,
+            summaries,
             started_at: Utc::now(),
             ended_at: None,
         }
     }
+
+    pub fn summary(&self, player_id: Uuid) -> Option<&ExpeditionSummary> {
+        self.summaries.iter().find(|summary| summary.player_id == player_id)
+    }
+
+    pub fn summary_mut(&mut self, player_id: Uuid) -> Option<&mut ExpeditionSummary> {
+        self.summaries.iter_mut().find(|summary| summary.player_id == player_id)
+    }
 }
 
 impl super::Model for Expedition {
@@ -29,7 +50,70 @@ impl super::Model for Expedition {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExpeditionSummary {
+    pub player_id: Uuid,
+    pub exp: u64,
+    pub cin: u64,
+    pub items_found: u64,
+    pub energy_spent: u64,
+    pub end_reason: Option<ExpeditionEndReason>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl ExpeditionSummary {
+    pub fn new(player_id: Uuid) -> Self {
+        Self {
+            player_id,
+            exp: 0,
+            cin: 0,
+            items_found: 0,
+            energy_spent: 0,
+            end_reason: None,
+            ended_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExpeditionEndReason {
+    Manual,
+    OutOfEnergy,
+    Death,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ExpeditionKind {
     Hunt,
-}
\ No newline at end of file
+    Gathering,
+    Mining,
+    Fishing,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ExpeditionDifficulty {
+    Novice,
+    Adept,
+    Veteran,
+    Elite,
+}
+
+impl ExpeditionDifficulty {
+    pub fn for_compass_level(level: u32) -> Self {
+        match level {
+            0..=2 => ExpeditionDifficulty::Novice,
+            3..=4 => ExpeditionDifficulty::Adept,
+            5..=6 => ExpeditionDifficulty::Veteran,
+            _ => ExpeditionDifficulty::Elite,
+        }
+    }
+
+    pub fn monster_level_bonus(&self) -> u8 {
+        match self {
+            ExpeditionDifficulty::Novice => 0,
+            ExpeditionDifficulty::Adept => 2,
+            ExpeditionDifficulty::Veteran => 4,
+            ExpeditionDifficulty::Elite => 6,
+        }
+    }
+}
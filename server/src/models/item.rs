@@ -1,6 +1,8 @@
+use crate::meta::{ItemCatalog, MAX_CARRY_PERCENT};
 use crate::models::item_stats::ItemStats;
-use crate::models::{Slot, SlotKind};
+use crate::models::{ItemEffect, ItemRequirements, ItemRune, PlayerAttributes, Slot, SlotKind};
 use crate::server::GameServer;
+use crate::services::encumbrance::Encumbrance;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -8,6 +10,7 @@ use uuid::Uuid;
 pub struct Item {
     pub id: Uuid,
     pub player_id: Uuid,
+    pub template_id: String,
     pub kind: ItemKind,
     pub name: String,
     pub tier: ItemTier,
@@ -20,45 +23,78 @@ pub struct Item {
     pub is_stackable: bool,
     pub is_usable: bool,
     pub stats: Option<ItemStats>,
+    pub requirements: Option<ItemRequirements>,
+    pub effect: Option<ItemEffect>,
+    pub rune: Option<ItemRune>,
 }
 
 impl Item {
-    pub fn new(
-        player_id: Uuid,
-        kind: ItemKind,
-        name: &str,
-        tier: ItemTier,
-        icon: &str,
-        quantity: u64,
-        level: u32,
-        enchanted: u32,
-        description: &str,
-        weight: f32,
-        is_stackable: bool,
-        is_usable: bool,
-        stats: Option<ItemStats>,
-    ) -> Self {
-        Self {
-            id: Uuid::new_v4(),
-            player_id,
-            kind,
-            name: name.to_string(),
-            icon: icon.to_string(),
-            tier,
-            quantity,
-            level,
-            enchanted,
-            description: description.to_string(),
-            weight,
-            is_stackable,
-            is_usable,
-            stats,
+    pub fn from_template(player_id: Uuid, template_id: &str, quantity: u64) -> Result<Self, String> {
+        let template = ItemCatalog::global()
+            .get(template_id)
+            .ok_or_else(|| format!("Unknown item template '{}'", template_id))?;
+
+        Ok(template.create(player_id, quantity))
+    }
+
+    pub fn can_equip(&self, slot_kind: &SlotKind, player_level: u8, attributes: &PlayerAttributes) -> Result<(), String> {
+        if self.kind.slot_kind().as_ref() != Some(slot_kind) {
+            return Err(format!("{} cannot be placed in a {:?} slot", self.name, slot_kind));
+        }
+
+        if (player_level as u32) < self.level {
+            return Err(format!(
+                "{} requires level {} (you are level {})",
+                self.name, self.level, player_level
+            ));
+        }
+
+        let Some(requirements) = &self.requirements else {
+            return Ok(());
+        };
+
+        let checks = [
+            ("strength", requirements.strength, attributes.strength),
+            ("dexterity", requirements.dexterity, attributes.dexterity),
+            ("vitality", requirements.vitality, attributes.vitality),
+            ("intelligence", requirements.intelligence, attributes.intelligence),
+            ("spirit", requirements.spirit, attributes.spirit),
+            ("luck", requirements.luck, attributes.luck),
+        ];
+
+        for (name, required, current) in checks {
+            if let Some(required) = required
+                && current < required {
+                return Err(format!(
+                    "{} requires {} {} (you have {})",
+                    self.name, required, name, current
+                ));
+            }
         }
+
+        Ok(())
+    }
+
+    pub fn stacks_with(&self, other: &Item) -> bool {
+        self.is_stackable
+            && other.is_stackable
+            && self.kind == other.kind
+            && self.name == other.name
+            && self.level == other.level
+            && self.enchanted == other.enchanted
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.weight * self.quantity as f32
     }
 
     pub fn add_to_empty_slot(&self, kind: SlotKind) -> Result<(), String> {
         let server = GameServer::global();
 
+        if kind.counts_toward_weight() {
+            Encumbrance::ensure_capacity(self.player_id, self.total_weight(), MAX_CARRY_PERCENT)?;
+        }
+
         let mut slots: Vec<Slot> = server.slots_store
             .find_all_by(|slot| {
                 slot.player_id == self.player_id && slot.kind == kind
@@ -68,20 +104,15 @@ impl Item {
 
         if self.is_stackable {
             for slot in &slots {
-                if let Some(existing_item) = &slot.item {
-                    if existing_item.is_stackable
-                        && existing_item.kind == self.kind
-                        && existing_item.name == self.name
-                        && existing_item.level == self.level
-                        && existing_item.enchanted == self.enchanted {
-                        server.slots_store.update(&slot.id, |slot| {
-                            if let Some(ref mut item) = slot.item {
-                                item.quantity += self.quantity;
-                            }
-                        })?;
-
-                        return Ok(());
-                    }
+                if let Some(existing_item) = &slot.item
+                    && existing_item.stacks_with(self) {
+                    server.slots_store.update(&slot.id, |slot| {
+                        if let Some(ref mut item) = slot.item {
+                            item.quantity += self.quantity;
+                        }
+                    })?;
+
+                    return Ok(());
                 }
             }
         }
@@ -105,7 +136,7 @@ impl super::Model for Item {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ItemKind {
     Rune,
     Currency,
@@ -126,9 +157,39 @@ pub enum ItemKind {
     Earring,
     Necklace,
     Pendant,
+    Material,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+impl ItemKind {
+    pub fn is_enchantable(&self) -> bool {
+        !matches!(self, ItemKind::Rune | ItemKind::Currency | ItemKind::Consumable | ItemKind::Hand | ItemKind::Material)
+    }
+
+    pub fn slot_kind(&self) -> Option<SlotKind> {
+        match self {
+            ItemKind::Rune => Some(SlotKind::Rune),
+            ItemKind::Consumable => Some(SlotKind::Consumable),
+            ItemKind::Compass => Some(SlotKind::Compass),
+            ItemKind::Weapon => Some(SlotKind::Weapon),
+            ItemKind::Shoulders => Some(SlotKind::Shoulders),
+            ItemKind::Helmet => Some(SlotKind::Helmet),
+            ItemKind::Mask => Some(SlotKind::Mask),
+            ItemKind::Cloak => Some(SlotKind::Cloak),
+            ItemKind::Armor => Some(SlotKind::Armor),
+            ItemKind::Belt => Some(SlotKind::Belt),
+            ItemKind::Gloves => Some(SlotKind::Gloves),
+            ItemKind::Pants => Some(SlotKind::Pants),
+            ItemKind::Boots => Some(SlotKind::Boots),
+            ItemKind::Ring => Some(SlotKind::Ring),
+            ItemKind::Earring => Some(SlotKind::Earring),
+            ItemKind::Necklace => Some(SlotKind::Necklace),
+            ItemKind::Pendant => Some(SlotKind::Pendant),
+            ItemKind::Currency | ItemKind::Hand | ItemKind::Material => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ItemTier {
     Common,
     Uncommon,
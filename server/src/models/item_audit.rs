@@ -0,0 +1,50 @@
+use crate::models::{Item, ItemTier, SlotKind};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemAudit {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub item_id: Uuid,
+    pub template_id: String,
+    pub item_name: String,
+    pub tier: ItemTier,
+    pub level: u32,
+    pub enchanted: u32,
+    pub quantity: u64,
+    pub slot_kind: SlotKind,
+    pub action: ItemAuditAction,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ItemAudit {
+    pub fn new(player_id: Uuid, item: &Item, quantity: u64, slot_kind: SlotKind, action: ItemAuditAction) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            item_id: item.id,
+            template_id: item.template_id.clone(),
+            item_name: item.name.clone(),
+            tier: item.tier.clone(),
+            level: item.level,
+            enchanted: item.enchanted,
+            quantity,
+            slot_kind,
+            action,
+            created_at: Utc::now(),
+        }
+    }
+}
+
+impl super::Model for ItemAudit {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ItemAuditAction {
+    Destroyed,
+}
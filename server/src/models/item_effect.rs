@@ -0,0 +1,9 @@
+use crate::models::BuffKind;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ItemEffect {
+    RestoreEnergy { amount: u64 },
+    RestoreHp { amount: u64 },
+    Buff { kind: BuffKind, value: f32, duration_secs: u64 },
+}
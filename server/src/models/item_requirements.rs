@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemRequirements {
+    pub strength: Option<u32>,
+    pub dexterity: Option<u32>,
+    pub vitality: Option<u32>,
+    pub intelligence: Option<u32>,
+    pub spirit: Option<u32>,
+    pub luck: Option<u32>,
+}
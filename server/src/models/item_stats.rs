@@ -10,23 +10,3 @@ pub struct ItemStats {
     pub energy_regeneration_interval: Option<u64>,
     pub expedition_kind: Option<ExpeditionKind>,
 }
-
-impl ItemStats {
-    pub fn new(
-        attack: Option<u64>,
-        attack_speed: Option<u64>,
-        defense: Option<u64>,
-        energy_regeneration: Option<u64>,
-        energy_regeneration_interval: Option<u64>,
-        expedition_kind: Option<ExpeditionKind>,
-    ) -> Self {
-        Self {
-            attack,
-            attack_speed,
-            defense,
-            energy_regeneration,
-            energy_regeneration_interval,
-            expedition_kind,
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use crate::models::Item;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Mail {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub sender: String,
+    pub subject: String,
+    pub body: String,
+    pub items: Vec<Item>,
+    pub cin: u64,
+    pub read: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Mail {
+    pub fn new(player_id: Uuid, sender: &str, subject: String, body: String, items: Vec<Item>, cin: u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            sender: sender.to_string(),
+            subject,
+            body,
+            items: items
+                .into_iter()
+                .map(|item| Item { player_id, ..item })
+                .collect(),
+            cin,
+            read: false,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn has_attachments(&self) -> bool {
+        !self.items.is_empty() || self.cin > 0
+    }
+}
+
+impl super::Model for Mail {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
@@ -0,0 +1,43 @@
+use crate::models::Item;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MarketListing {
+    pub id: Uuid,
+    pub seller_id: Uuid,
+    pub seller_name: String,
+    pub item: Item,
+    pub price: u64,
+    pub fee: u64,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl MarketListing {
+    pub fn new(seller_id: Uuid, seller_name: String, item: Item, price: u64, fee: u64, duration_hours: u64) -> Self {
+        let created_at = Utc::now();
+
+        Self {
+            id: Uuid::new_v4(),
+            seller_id,
+            seller_name,
+            item,
+            price,
+            fee,
+            created_at,
+            expires_at: created_at + Duration::hours(duration_hours as i64),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+impl super::Model for MarketListing {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
@@ -7,23 +7,55 @@ mod player_state;
 mod player_stats;
 mod chat_message;
 mod item_stats;
+mod item_requirements;
 mod expedition;
 mod log;
+mod buff;
+mod item_effect;
+mod rune;
+mod enchant_attempt;
+mod party;
+mod shop_stock;
+mod buyback;
+mod trade;
+mod market_listing;
+mod mail;
+mod salvage_record;
+mod item_audit;
+mod destroy_confirmation;
 
+pub use buff::Buff;
+pub use buyback::Buyback;
 pub use chat_message::ChatMessage;
+pub use destroy_confirmation::DestroyConfirmation;
+pub use enchant_attempt::{EnchantAttempt, EnchantMaterial, EnchantOutcome};
 pub use expedition::Expedition;
 pub use item::Item;
+pub use item_audit::{ItemAudit, ItemAuditAction};
+pub use item_effect::ItemEffect;
+pub use item_requirements::ItemRequirements;
 pub use item_stats::ItemStats;
 pub use log::Log;
+pub use mail::Mail;
+pub use market_listing::MarketListing;
+pub use party::{Party, PartyInvitation, PartyLootRule};
 pub use player::Player;
 pub use player_attributes::PlayerAttributes;
 pub use player_resource::PlayerResource;
 pub use player_state::PlayerState;
 pub use player_stats::PlayerStats;
+pub use rune::{ItemRune, RuneFamily, RuneModifier, RuneTotals};
+pub use salvage_record::{SalvageRecord, SalvagedItem};
+pub use shop_stock::ShopStock;
 pub use slot::Slot;
+pub use trade::Trade;
 
+pub use buff::BuffKind;
 pub use chat_message::ChatKind;
+pub use expedition::ExpeditionDifficulty;
+pub use expedition::ExpeditionEndReason;
 pub use expedition::ExpeditionKind;
+pub use expedition::ExpeditionSummary;
 pub use item::ItemKind;
 pub use item::ItemTier;
 pub use slot::SlotKind;
@@ -0,0 +1,76 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Party {
+    pub id: Uuid,
+    pub leader_id: Uuid,
+    pub members: Vec<Uuid>,
+    pub invites: Vec<PartyInvitation>,
+    pub loot_rule: PartyLootRule,
+    pub round_robin_index: usize,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Party {
+    pub fn new(leader_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            leader_id,
+            members: vec![leader_id],
+            invites: vec![],
+            loot_rule: PartyLootRule::Equal,
+            round_robin_index: 0,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn is_leader(&self, player_id: Uuid) -> bool {
+        self.leader_id == player_id
+    }
+
+    pub fn is_invited(&self, player_id: Uuid) -> bool {
+        self.invites.iter().any(|invite| invite.player_id == player_id && !invite.is_expired())
+    }
+
+    pub fn pending_invites(&self) -> usize {
+        self.invites.iter().filter(|invite| !invite.is_expired()).count()
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        self.members.len() <= 1 && self.pending_invites() == 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartyInvitation {
+    pub player_id: Uuid,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PartyInvitation {
+    pub fn new(player_id: Uuid, invite_secs: i64) -> Self {
+        Self {
+            player_id,
+            expires_at: Utc::now() + Duration::seconds(invite_secs),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+impl super::Model for Party {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PartyLootRule {
+    Equal,
+    RoundRobin,
+    NeedGreed,
+}
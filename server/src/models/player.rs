@@ -11,6 +11,7 @@ pub struct Player {
     #[serde(skip_serializing)]
     pub password_hash: String,
     pub exp: u64,
+    pub level: u8,
     pub timestamp: DateTime<Utc>,
 }
 
@@ -25,6 +26,7 @@ impl Player {
             email,
             password_hash,
             exp: 0,
+            level: 1,
             timestamp: Utc::now(),
         })
     }
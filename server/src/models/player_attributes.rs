@@ -11,6 +11,7 @@ pub struct PlayerAttributes {
     pub intelligence: u32,
     pub spirit: u32,
     pub luck: u32,
+    pub unspent_points: u32,
 }
 
 impl PlayerAttributes {
@@ -24,6 +25,7 @@ impl PlayerAttributes {
             intelligence,
             spirit,
             luck,
+            unspent_points: 0,
         }
     }
 }
@@ -8,16 +8,22 @@ pub struct PlayerResource {
     pub energy: u64,
     pub max_energy: u64,
     pub weight_limit: u64,
+    pub carried_weight: f32,
+    pub hp: u64,
+    pub max_hp: u64,
 }
 
 impl PlayerResource {
-    pub fn new(player_id: Uuid) -> Self {
+    pub fn new(player_id: Uuid, max_hp: u64) -> Self {
         Self {
             id: Uuid::new_v4(),
             player_id,
             energy: 500,
             max_energy: 500,
             weight_limit: 100,
+            carried_weight: 0.0,
+            hp: max_hp,
+            max_hp,
         }
     }
 }
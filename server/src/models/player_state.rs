@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -6,6 +7,8 @@ pub struct PlayerState {
     pub id: Uuid,
     pub player_id: Uuid,
     pub is_looting: bool,
+    pub respawn_at: Option<DateTime<Utc>>,
+    pub offline_since: Option<DateTime<Utc>>,
 }
 
 impl PlayerState {
@@ -14,6 +17,8 @@ impl PlayerState {
             id: Uuid::new_v4(),
             player_id,
             is_looting: false,
+            respawn_at: None,
+            offline_since: None,
         }
     }
 }
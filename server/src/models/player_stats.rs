@@ -1,7 +1,9 @@
 use crate::messages::{OutgoingEvent, OutgoingMessage};
-use crate::meta::{BASE_ATTACK_SPEED, BASE_HP_REGENERATION, BASE_HP_REGENERATION_INTERVAL};
-use crate::models::PlayerAttributes;
+use crate::meta::{BASE_ATTACK_SPEED, BASE_ENERGY_REGENERATION, BASE_ENERGY_REGENERATION_INTERVAL, BASE_HP, BASE_HP_REGENERATION, BASE_HP_REGENERATION_INTERVAL, MIN_ATTACK_SPEED};
+use crate::models::{BuffKind, PlayerAttributes};
 use crate::server::{GameServer, WebSocketManager};
+use crate::services::buffs::Buffs;
+use crate::services::runes::Runes;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -14,6 +16,9 @@ pub struct PlayerStats {
     pub defense: u64,
     pub energy_regeneration: u64,
     pub energy_regeneration_interval: u64,
+    pub max_hp: u64,
+    pub hp_regeneration: u64,
+    pub hp_regeneration_interval: u64,
 }
 
 impl PlayerStats {
@@ -31,6 +36,9 @@ impl PlayerStats {
             defense: Self::calculate_defense(&attributes),
             energy_regeneration: Self::calculate_energy_regeneration(&attributes),
             energy_regeneration_interval: Self::calculate_energy_regeneration_interval(&attributes),
+            max_hp: Self::calculate_max_hp(&attributes),
+            hp_regeneration: Self::calculate_hp_regeneration(&attributes),
+            hp_regeneration_interval: Self::calculate_hp_regeneration_interval(&attributes),
         }
     }
 
@@ -47,7 +55,11 @@ impl PlayerStats {
 
         let equipment_attack = Self::get_equipment_stat(attributes.player_id, |stats| stats.attack.unwrap_or(0));
 
-        base_total + equipment_attack
+        let runes = Runes::totals(attributes.player_id);
+
+        let multiplier = 1.0 + Buffs::total(attributes.player_id, BuffKind::Attack) + runes.attack_percent;
+
+        ((base_total + equipment_attack + runes.attack) as f32 * multiplier).round() as u64
     }
 
     fn calculate_attack_speed(attributes: &PlayerAttributes) -> u64 {
@@ -55,19 +67,23 @@ impl PlayerStats {
         let dex_reduction = attributes.dexterity as u64 * 10;
         let strength_penalty = attributes.strength as u64 * 5;
 
-        let calculated_speed = base_attack_speed + strength_penalty - dex_reduction;
+        let calculated_speed = (base_attack_speed + strength_penalty).saturating_sub(dex_reduction);
 
         let base_speed = calculated_speed.max(500);
 
-        let equipment_speed_modifier = Self::get_equipment_stat(attributes.player_id, |stats| stats.attack_speed.unwrap_or(0) as u64);
+        let equipment_speed_modifier = Self::get_equipment_stat(attributes.player_id, |stats| stats.attack_speed.unwrap_or(0));
+
+        let buff_multiplier = 1.0 + Buffs::total(attributes.player_id, BuffKind::AttackSpeed);
 
-        base_speed - equipment_speed_modifier
+        let equipped_speed = base_speed.saturating_sub(equipment_speed_modifier).max(MIN_ATTACK_SPEED);
+
+        (equipped_speed as f32 / buff_multiplier.max(0.1)).round() as u64
     }
 
     fn calculate_defense(attributes: &PlayerAttributes) -> u64 {
         let base_defense = 10;
         let strength_bonus = attributes.strength as u64 * 3;
-        let vit_bonus = attributes.vitality as u64 * 1;
+        let vit_bonus = attributes.vitality as u64;
 
         let flat_attack = base_defense + strength_bonus + vit_bonus;
 
@@ -77,15 +93,19 @@ impl PlayerStats {
 
         let equipment_attack = Self::get_equipment_stat(attributes.player_id, |stats| stats.defense.unwrap_or(0));
 
-        base_total + equipment_attack
+        let runes = Runes::totals(attributes.player_id);
+
+        let multiplier = 1.0 + Buffs::total(attributes.player_id, BuffKind::Defense) + runes.defense_percent;
+
+        ((base_total + equipment_attack + runes.defense) as f32 * multiplier).round() as u64
     }
 
     fn calculate_energy_regeneration(attributes: &PlayerAttributes) -> u64 {
-        let base_hp_regen = BASE_HP_REGENERATION;
+        let base_energy_regen = BASE_ENERGY_REGENERATION;
         let vitality_bonus = attributes.vitality as u64 / 10;
         let spirit_bonus = attributes.spirit as u64 / 15;
 
-        let base_total = base_hp_regen + vitality_bonus + spirit_bonus;
+        let base_total = base_energy_regen + vitality_bonus + spirit_bonus;
 
         let equipment_regen = Self::get_equipment_stat(attributes.player_id, |stats| stats.energy_regeneration.unwrap_or(0));
 
@@ -93,16 +113,39 @@ impl PlayerStats {
     }
 
     fn calculate_energy_regeneration_interval(attributes: &PlayerAttributes) -> u64 {
-        let base_interval = BASE_HP_REGENERATION_INTERVAL;
-        let vitality_reduction = attributes.vitality as u64 * 1;
-        let spirit_reduction = attributes.spirit as u64 * 1;
+        let base_interval = BASE_ENERGY_REGENERATION_INTERVAL;
+        let vitality_reduction = attributes.vitality as u64;
+        let spirit_reduction = attributes.spirit as u64;
 
-        let calculated_interval = base_interval - vitality_reduction - spirit_reduction;
+        let calculated_interval = base_interval.saturating_sub(vitality_reduction + spirit_reduction);
         let base_interval = calculated_interval.max(300);
 
         let equipment_interval_modifier = Self::get_equipment_stat(attributes.player_id, |stats| stats.energy_regeneration_interval.unwrap_or(0));
 
-        (base_interval - equipment_interval_modifier).max(300)
+        base_interval.saturating_sub(equipment_interval_modifier).max(300)
+    }
+
+    fn calculate_max_hp(attributes: &PlayerAttributes) -> u64 {
+        let vitality_bonus = attributes.vitality as u64 * 12;
+        let defense_bonus = Self::calculate_defense(attributes) / 2;
+
+        BASE_HP + vitality_bonus + defense_bonus
+    }
+
+    fn calculate_hp_regeneration(attributes: &PlayerAttributes) -> u64 {
+        let vitality_bonus = attributes.vitality as u64 / 5;
+        let spirit_bonus = attributes.spirit as u64 / 10;
+
+        BASE_HP_REGENERATION + vitality_bonus + spirit_bonus
+    }
+
+    fn calculate_hp_regeneration_interval(attributes: &PlayerAttributes) -> u64 {
+        let vitality_reduction = attributes.vitality as u64 * 5;
+        let spirit_reduction = attributes.spirit as u64 * 2;
+
+        BASE_HP_REGENERATION_INTERVAL
+            .saturating_sub(vitality_reduction + spirit_reduction)
+            .max(500)
     }
 
     pub fn recalculate(&self) -> Result<PlayerStats, String> {
@@ -118,8 +161,23 @@ impl PlayerStats {
             stats.defense = Self::calculate_defense(&attributes);
             stats.energy_regeneration = Self::calculate_energy_regeneration(&attributes);
             stats.energy_regeneration_interval = Self::calculate_energy_regeneration_interval(&attributes);
+            stats.max_hp = Self::calculate_max_hp(&attributes);
+            stats.hp_regeneration = Self::calculate_hp_regeneration(&attributes);
+            stats.hp_regeneration_interval = Self::calculate_hp_regeneration_interval(&attributes);
         });
 
+        let updated_resource = match &updated {
+            Ok(stats) => server.player_resource_store
+                .find_by(|r| r.player_id == self.player_id)
+                .and_then(|resource| {
+                    server.player_resource_store.update(&resource.id, |resource| {
+                        resource.max_hp = stats.max_hp;
+                        resource.hp = resource.hp.min(stats.max_hp);
+                    }).ok()
+                }),
+            Err(_) => None,
+        };
+
         let player_id = self.player_id;
         let result = updated.clone();
 
@@ -133,6 +191,15 @@ impl PlayerStats {
                 );
                 ws.send_to_player(player_id, msg).await;
             }
+
+            if let Some(resource) = updated_resource {
+                let ws = WebSocketManager::global();
+                let msg = OutgoingMessage::new(
+                    OutgoingEvent::PlayerResource,
+                    Box::new(resource) as Box<dyn erased_serde::Serialize + Send>,
+                );
+                ws.send_to_player(player_id, msg).await;
+            }
         });
 
         result
@@ -166,10 +233,9 @@ impl PlayerStats {
         let mut total_stat = 0;
 
         for slot in equipment_slots {
-            if let Some(item) = &slot.item {
-                if let Some(stats) = &item.stats {
-                    total_stat += stat_extractor(stats);
-                }
+            if let Some(item) = &slot.item
+                && let Some(stats) = &item.stats {
+                total_stat += stat_extractor(stats);
             }
         }
 
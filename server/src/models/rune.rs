@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ItemRune {
+    pub family: RuneFamily,
+    pub modifiers: Vec<RuneModifier>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RuneFamily {
+    Fang,
+    Bark,
+    Stone,
+    Star,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RuneModifier {
+    Attack(u64),
+    AttackPercent(f32),
+    Defense(u64),
+    DefensePercent(f32),
+    ExpChance(f32),
+    LootChance(f32),
+    CinChance(f32),
+    EnergyCostReduction(f32),
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RuneTotals {
+    pub attack: u64,
+    pub attack_percent: f32,
+    pub defense: u64,
+    pub defense_percent: f32,
+    pub exp_chance: f32,
+    pub loot_chance: f32,
+    pub cin_chance: f32,
+    pub energy_cost_reduction: f32,
+}
+
+impl RuneTotals {
+    pub fn add(&mut self, modifier: &RuneModifier) {
+        match modifier {
+            RuneModifier::Attack(value) => self.attack += value,
+            RuneModifier::AttackPercent(value) => self.attack_percent += value,
+            RuneModifier::Defense(value) => self.defense += value,
+            RuneModifier::DefensePercent(value) => self.defense_percent += value,
+            RuneModifier::ExpChance(value) => self.exp_chance += value,
+            RuneModifier::LootChance(value) => self.loot_chance += value,
+            RuneModifier::CinChance(value) => self.cin_chance += value,
+            RuneModifier::EnergyCostReduction(value) => self.energy_cost_reduction += value,
+        }
+    }
+}
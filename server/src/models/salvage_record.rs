@@ -0,0 +1,46 @@
+use crate::models::{Item, SlotKind};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SalvageRecord {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub salvaged: Vec<SalvagedItem>,
+    pub materials: Vec<(String, u64)>,
+    pub created_at: DateTime<Utc>,
+    pub undo_until: DateTime<Utc>,
+}
+
+impl SalvageRecord {
+    pub fn new(player_id: Uuid, salvaged: Vec<SalvagedItem>, materials: Vec<(String, u64)>, undo_secs: i64) -> Self {
+        let created_at = Utc::now();
+
+        Self {
+            id: Uuid::new_v4(),
+            player_id,
+            salvaged,
+            materials,
+            created_at,
+            undo_until: created_at + Duration::seconds(undo_secs),
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        Utc::now() < self.undo_until
+    }
+}
+
+impl super::Model for SalvageRecord {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SalvagedItem {
+    pub item: Item,
+    pub kind: SlotKind,
+    pub index: u64,
+}
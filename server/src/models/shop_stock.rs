@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShopStock {
+    pub id: Uuid,
+    pub template_id: String,
+    pub remaining: u64,
+    pub restocked_at: DateTime<Utc>,
+}
+
+impl ShopStock {
+    pub fn new(template_id: String, remaining: u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            template_id,
+            remaining,
+            restocked_at: Utc::now(),
+        }
+    }
+}
+
+impl super::Model for ShopStock {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
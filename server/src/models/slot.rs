@@ -71,4 +71,11 @@ pub enum SlotKind {
     Necklace,
     Pendant,
     Ground,
-}
\ No newline at end of file
+    Stash,
+}
+
+impl SlotKind {
+    pub fn counts_toward_weight(&self) -> bool {
+        !matches!(self, SlotKind::Ground | SlotKind::Stash)
+    }
+}
@@ -0,0 +1,74 @@
+use crate::models::Item;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Trade {
+    pub id: Uuid,
+    pub requester_id: Uuid,
+    pub partner_id: Uuid,
+    pub accepted: bool,
+    pub requester_offer: TradeOffer,
+    pub partner_offer: TradeOffer,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Trade {
+    pub fn new(requester_id: Uuid, partner_id: Uuid) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            requester_id,
+            partner_id,
+            accepted: false,
+            requester_offer: TradeOffer::default(),
+            partner_offer: TradeOffer::default(),
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn involves(&self, player_id: Uuid) -> bool {
+        self.requester_id == player_id || self.partner_id == player_id
+    }
+
+    pub fn other(&self, player_id: Uuid) -> Uuid {
+        if self.requester_id == player_id { self.partner_id } else { self.requester_id }
+    }
+
+    pub fn offer(&self, player_id: Uuid) -> &TradeOffer {
+        if self.requester_id == player_id { &self.requester_offer } else { &self.partner_offer }
+    }
+
+    pub fn offer_mut(&mut self, player_id: Uuid) -> &mut TradeOffer {
+        if self.requester_id == player_id { &mut self.requester_offer } else { &mut self.partner_offer }
+    }
+
+    pub fn reset_locks(&mut self) {
+        for offer in [&mut self.requester_offer, &mut self.partner_offer] {
+            offer.locked = false;
+            offer.confirmed = false;
+        }
+    }
+
+    pub fn both_locked(&self) -> bool {
+        self.requester_offer.locked && self.partner_offer.locked
+    }
+
+    pub fn both_confirmed(&self) -> bool {
+        self.requester_offer.confirmed && self.partner_offer.confirmed
+    }
+}
+
+impl super::Model for Trade {
+    fn id(&self) -> Uuid {
+        self.id
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TradeOffer {
+    pub items: Vec<Item>,
+    pub cin: u64,
+    pub locked: bool,
+    pub confirmed: bool,
+}
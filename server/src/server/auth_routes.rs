@@ -1,5 +1,6 @@
 use crate::auth;
-use crate::models::{ExpeditionKind, Item, ItemKind, ItemStats, ItemTier, Player, PlayerAttributes, PlayerResource, PlayerState, PlayerStats, Slot, SlotKind};
+use crate::meta::STASH_TAB_SIZE;
+use crate::models::{Item, Player, PlayerAttributes, PlayerResource, PlayerState, PlayerStats, Slot, SlotKind};
 use crate::server::GameServer;
 use axum::extract::State;
 use axum::http::StatusCode;
@@ -50,10 +51,6 @@ pub async fn register(
     server.player_store.insert(player)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
-    let player_resource = PlayerResource::new(player_id);
-    server.player_resource_store.insert(player_resource)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-
     let player_attributes = PlayerAttributes::new(player_id, 10, 7, 6, 5, 5, 3);
     server.player_attributes_store.insert(player_attributes)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
@@ -63,15 +60,20 @@ pub async fn register(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
     let player_stats = PlayerStats::new(player_id);
+    let player_resource = PlayerResource::new(player_id, player_stats.max_hp);
     server.player_stats_store.insert(player_stats)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
 
+    server.player_resource_store.insert(player_resource)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+
     for kind in SlotKind::iter() {
         let qty = match kind {
             SlotKind::Inventory => 56,
             SlotKind::Rune => 24,
             SlotKind::Consumable => 12,
             SlotKind::Ground => 52,
+            SlotKind::Stash => STASH_TAB_SIZE,
             SlotKind::Ring | SlotKind::Earring => 2,
             _ => 1,
         };
@@ -83,47 +85,14 @@ pub async fn register(
         }
     }
 
-    let training_sword_stats = ItemStats::new(Some(20), Some(200), None, None, None, None);
-    let training_sword = Item::new(
-        player_id,
-        ItemKind::Weapon,
-        "Training Sword",
-        ItemTier::Common,
-        "game-icons:broadsword",
-        1,
-        0,
-        0,
-        "Basic wooden sword used for beginner combat training. Light and perfect for practice.",
-        2f32,
-        false,
-        false,
-        Some(training_sword_stats),
-    );
-    server.items_store.insert(training_sword.clone())
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-    training_sword.add_to_empty_slot(SlotKind::Inventory)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-
-    let hunter_compass_stats = ItemStats::new(None, None, None, None, None, Some(ExpeditionKind::Hunt));
-    let hunter_compass = Item::new(
-        player_id,
-        ItemKind::Compass,
-        "Hunter Compass",
-        ItemTier::Common,
-        "game-icons:compass",
-        1,
-        1,
-        0,
-        "A compass used for hunting. It can be used to track down a target and find their location.",
-        1f32,
-        false,
-        false,
-        Some(hunter_compass_stats),
-    );
-    server.items_store.insert(hunter_compass.clone())
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
-    hunter_compass.add_to_empty_slot(SlotKind::Inventory)
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    for template_id in ["training_sword", "hunter_compass"] {
+        let item = Item::from_template(player_id, template_id, 1)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        server.items_store.insert(item.clone())
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        item.add_to_empty_slot(SlotKind::Inventory)
+            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+    }
 
     let token = auth::create_token(player_id, username.clone())
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
@@ -1,8 +1,24 @@
-use crate::messages::{DropItem, IncomingEvent, OutgoingEvent, OutgoingMessage, SendChatMessage, TakeItem};
-use crate::models::{ChatKind, ChatMessage, Expedition, ExpeditionKind, ItemKind, PlayerState, SlotKind};
+use crate::messages::{AcceptPartyInvite, AcceptTrade, AddTradeItem, AllocateAttributes, BuyItem, BuyMarketListing, BuybackItem, CancelMarketListing, ClaimAttachments, Craft, DeclinePartyInvite, DeleteMail, DestroyItem, DropItem, EnchantItem, ExpeditionHistory, IncomingEvent, InviteToParty, KickFromParty, ListMarketItem, MoveItem, OutgoingEvent, OutgoingMessage, PartyInvite, ReadMail, RemoveTradeItem, RequestTrade, SalvageBelowTier, SalvageItem, SearchMarket, SellItem, SendChatMessage, SetPartyLootRule, SetTradeCin, SortInventory, SplitStack, TakeItem, TradeRequest, UndoSalvage, UpgradeCompass, UseItem};
+use crate::meta::{ItemCatalog, ShopCatalog, MAX_OVERWEIGHT_PERCENT};
+use crate::models::{ChatKind, ChatMessage, EnchantOutcome, Expedition, ExpeditionDifficulty, ExpeditionEndReason, ItemEffect, ItemKind, Party, PlayerState, SalvageRecord, SlotKind};
 use crate::server::websocket_manager::WebSocketManager;
 use crate::server::GameServer;
-use chrono::Utc;
+use crate::services::buffs::Buffs;
+use crate::services::compass_upgrader::CompassUpgrader;
+use crate::services::crafting::Crafting;
+use crate::services::enchanting::Enchanting;
+use crate::services::encumbrance::Encumbrance;
+use crate::services::expedition_service::ExpeditionService;
+use crate::services::inventory::Inventory;
+use crate::services::item_destroyer::{DestroyOutcome, ItemDestroyer};
+use crate::services::mailbox::Mailbox;
+use crate::services::market::{Market, MarketFilter};
+use crate::services::parties::Parties;
+use crate::services::runes::Runes;
+use crate::services::salvage::Salvage;
+use crate::services::shop::Shop;
+use crate::services::stash::Stash;
+use crate::services::trades::Trades;
 use uuid::Uuid;
 
 pub struct MessageHandler {
@@ -44,6 +60,192 @@ impl MessageHandler {
             IncomingEvent::ToggleLoot => {
                 self.handle_toggle_loot().await
             }
+            IncomingEvent::AllocateAttributes => {
+                let allocate_attributes: AllocateAttributes = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse AllocateAttributes data: {}", e))?;
+                self.handle_allocate_attributes(allocate_attributes).await
+            }
+            IncomingEvent::UseItem => {
+                let use_item: UseItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse UseItem data: {}", e))?;
+                self.handle_use_item(use_item).await
+            }
+            IncomingEvent::EnchantItem => {
+                let enchant_item: EnchantItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse EnchantItem data: {}", e))?;
+                self.handle_enchant_item(enchant_item).await
+            }
+            IncomingEvent::UpgradeCompass => {
+                let upgrade_compass: UpgradeCompass = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse UpgradeCompass data: {}", e))?;
+                self.handle_upgrade_compass(upgrade_compass).await
+            }
+            IncomingEvent::InviteToParty => {
+                let invite_to_party: InviteToParty = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse InviteToParty data: {}", e))?;
+                self.handle_invite_to_party(invite_to_party).await
+            }
+            IncomingEvent::AcceptPartyInvite => {
+                let accept_party_invite: AcceptPartyInvite = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse AcceptPartyInvite data: {}", e))?;
+                self.handle_accept_party_invite(accept_party_invite).await
+            }
+            IncomingEvent::DeclinePartyInvite => {
+                let decline_party_invite: DeclinePartyInvite = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse DeclinePartyInvite data: {}", e))?;
+                self.handle_decline_party_invite(decline_party_invite).await
+            }
+            IncomingEvent::LeaveParty => {
+                self.handle_leave_party().await
+            }
+            IncomingEvent::KickFromParty => {
+                let kick_from_party: KickFromParty = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse KickFromParty data: {}", e))?;
+                self.handle_kick_from_party(kick_from_party).await
+            }
+            IncomingEvent::SetPartyLootRule => {
+                let set_party_loot_rule: SetPartyLootRule = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SetPartyLootRule data: {}", e))?;
+                self.handle_set_party_loot_rule(set_party_loot_rule).await
+            }
+            IncomingEvent::ExpeditionHistory => {
+                let expedition_history: ExpeditionHistory = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse ExpeditionHistory data: {}", e))?;
+                self.handle_expedition_history(expedition_history).await
+            }
+            IncomingEvent::BuyItem => {
+                let buy_item: BuyItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse BuyItem data: {}", e))?;
+                self.handle_buy_item(buy_item).await
+            }
+            IncomingEvent::SellItem => {
+                let sell_item: SellItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SellItem data: {}", e))?;
+                self.handle_sell_item(sell_item).await
+            }
+            IncomingEvent::BuybackItem => {
+                let buyback_item: BuybackItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse BuybackItem data: {}", e))?;
+                self.handle_buyback_item(buyback_item).await
+            }
+            IncomingEvent::RequestTrade => {
+                let request_trade: RequestTrade = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse RequestTrade data: {}", e))?;
+                self.handle_request_trade(request_trade).await
+            }
+            IncomingEvent::AcceptTrade => {
+                let accept_trade: AcceptTrade = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse AcceptTrade data: {}", e))?;
+                self.handle_accept_trade(accept_trade).await
+            }
+            IncomingEvent::AddTradeItem => {
+                let add_trade_item: AddTradeItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse AddTradeItem data: {}", e))?;
+                self.handle_add_trade_item(add_trade_item).await
+            }
+            IncomingEvent::RemoveTradeItem => {
+                let remove_trade_item: RemoveTradeItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse RemoveTradeItem data: {}", e))?;
+                self.handle_remove_trade_item(remove_trade_item).await
+            }
+            IncomingEvent::SetTradeCin => {
+                let set_trade_cin: SetTradeCin = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SetTradeCin data: {}", e))?;
+                self.handle_set_trade_cin(set_trade_cin).await
+            }
+            IncomingEvent::LockTrade => {
+                self.handle_lock_trade().await
+            }
+            IncomingEvent::ConfirmTrade => {
+                self.handle_confirm_trade().await
+            }
+            IncomingEvent::CancelTrade => {
+                self.handle_cancel_trade().await
+            }
+            IncomingEvent::ListMarketItem => {
+                let list_market_item: ListMarketItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse ListMarketItem data: {}", e))?;
+                self.handle_list_market_item(list_market_item).await
+            }
+            IncomingEvent::BuyMarketListing => {
+                let buy_market_listing: BuyMarketListing = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse BuyMarketListing data: {}", e))?;
+                self.handle_buy_market_listing(buy_market_listing).await
+            }
+            IncomingEvent::CancelMarketListing => {
+                let cancel_market_listing: CancelMarketListing = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse CancelMarketListing data: {}", e))?;
+                self.handle_cancel_market_listing(cancel_market_listing).await
+            }
+            IncomingEvent::SearchMarket => {
+                let search_market: SearchMarket = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SearchMarket data: {}", e))?;
+                self.handle_search_market(search_market).await
+            }
+            IncomingEvent::ListMail => {
+                self.handle_list_mail().await
+            }
+            IncomingEvent::ReadMail => {
+                let read_mail: ReadMail = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse ReadMail data: {}", e))?;
+                self.handle_read_mail(read_mail).await
+            }
+            IncomingEvent::ClaimAttachments => {
+                let claim_attachments: ClaimAttachments = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse ClaimAttachments data: {}", e))?;
+                self.handle_claim_attachments(claim_attachments).await
+            }
+            IncomingEvent::DeleteMail => {
+                let delete_mail: DeleteMail = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse DeleteMail data: {}", e))?;
+                self.handle_delete_mail(delete_mail).await
+            }
+            IncomingEvent::Craft => {
+                let craft: Craft = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse Craft data: {}", e))?;
+                self.handle_craft(craft).await
+            }
+            IncomingEvent::SalvageItem => {
+                let salvage_item: SalvageItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SalvageItem data: {}", e))?;
+                self.handle_salvage_item(salvage_item).await
+            }
+            IncomingEvent::SalvageBelowTier => {
+                let salvage_below_tier: SalvageBelowTier = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SalvageBelowTier data: {}", e))?;
+                self.handle_salvage_below_tier(salvage_below_tier).await
+            }
+            IncomingEvent::UndoSalvage => {
+                let undo_salvage: UndoSalvage = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse UndoSalvage data: {}", e))?;
+                self.handle_undo_salvage(undo_salvage).await
+            }
+            IncomingEvent::SplitStack => {
+                let split_stack: SplitStack = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SplitStack data: {}", e))?;
+                self.handle_split_stack(split_stack).await
+            }
+            IncomingEvent::MoveItem => {
+                let move_item: MoveItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse MoveItem data: {}", e))?;
+                self.handle_move_item(move_item).await
+            }
+            IncomingEvent::SortInventory => {
+                let sort_inventory: SortInventory = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse SortInventory data: {}", e))?;
+                self.handle_sort_inventory(sort_inventory).await
+            }
+            IncomingEvent::CompactStacks => {
+                self.handle_compact_stacks().await
+            }
+            IncomingEvent::BuyStashTab => {
+                self.handle_buy_stash_tab().await
+            }
+            IncomingEvent::DestroyItem => {
+                let destroy_item: DestroyItem = serde_json::from_str(data)
+                    .map_err(|e| format!("Failed to parse DestroyItem data: {}", e))?;
+                self.handle_destroy_item(destroy_item).await
+            }
         }
     }
 
@@ -59,6 +261,10 @@ impl MessageHandler {
 
         let item = slot.item.as_ref().ok_or("No item in slot")?;
 
+        if data.kind == SlotKind::Stash {
+            Stash::ensure_accessible(self.player_id)?;
+        }
+
         if data.kind == SlotKind::Compass {
             let has_active_expedition = server.expeditions_store
                 .find_by(|e| e.participants.contains(&self.player_id) && e.ended_at.is_none())
@@ -81,6 +287,10 @@ impl MessageHandler {
             return Err("Hand is not empty".to_string());
         }
 
+        if !slot.kind.counts_toward_weight() {
+            Encumbrance::ensure_capacity(self.player_id, item.total_weight(), MAX_OVERWEIGHT_PERCENT)?;
+        }
+
         let item_to_move = item.clone();
 
         server.slots_store.update(&slot.id, |slot| {
@@ -91,19 +301,16 @@ impl MessageHandler {
             slot.item = Some(item_to_move);
         })?;
 
-        if slot.is_equipment_slot() {
-            if let Some(current_stats) = server.player_stats_store.find_by(|s| s.player_id == self.player_id) {
-                let _ = current_stats.recalculate();
-            }
+        if (slot.is_equipment_slot() || slot.kind == SlotKind::Rune)
+            && let Some(current_stats) = server.player_stats_store.find_by(|s| s.player_id == self.player_id) {
+            let _ = current_stats.recalculate();
         }
 
-        let mut slots = server.slots_store.find_all_by(|slot| slot.player_id == self.player_id);
-        slots.sort_by_key(|slot| slot.index);
+        if slot.kind == SlotKind::Rune {
+            Runes::send(self.player_id).await;
+        }
 
-        Ok(vec![OutgoingMessage::new(
-            OutgoingEvent::Slots,
-            Box::new(slots) as Box<dyn erased_serde::Serialize + Send>,
-        )])
+        self.inventory_update()
     }
 
     async fn handle_drop_item(&self, data: DropItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
@@ -131,13 +338,13 @@ impl MessageHandler {
             return Err("Cannot drop items on the ground".to_string());
         }
 
-        if data.kind == SlotKind::Inventory {
+        if target_slot.kind == SlotKind::Stash {
+            Stash::ensure_accessible(self.player_id)?;
+        }
+
+        if matches!(data.kind, SlotKind::Inventory | SlotKind::Stash) {
             if let Some(existing_item) = &target_slot.item {
-                if hand_item.is_stackable
-                    && existing_item.is_stackable
-                    && hand_item.kind == existing_item.kind
-                    && hand_item.name == existing_item.name
-                {
+                if hand_item.stacks_with(existing_item) {
                     let combined_quantity = hand_item.quantity + existing_item.quantity;
 
                     server.slots_store.update(&target_slot.id, |slot| {
@@ -145,7 +352,21 @@ impl MessageHandler {
                             item.quantity = combined_quantity;
                         }
                     })?;
+
+                    server.slots_store.update(&hand_slot.id, |slot| {
+                        slot.item = None;
+                    })?;
+
+                    let _ = server.items_store.remove(&hand_item.id);
                 } else {
+                    if target_slot.kind == SlotKind::Stash {
+                        Encumbrance::ensure_capacity(
+                            self.player_id,
+                            existing_item.total_weight() - hand_item.total_weight(),
+                            MAX_OVERWEIGHT_PERCENT,
+                        )?;
+                    }
+
                     let hand_item_clone = hand_item.clone();
                     let existing_item_clone = existing_item.clone();
 
@@ -161,19 +382,30 @@ impl MessageHandler {
                 server.slots_store.update(&target_slot.id, |slot| {
                     slot.item = Some(hand_item.clone());
                 })?;
+
+                server.slots_store.update(&hand_slot.id, |slot| {
+                    slot.item = None;
+                })?;
             }
-            server.slots_store.update(&hand_slot.id, |slot| {
-                slot.item = None;
-            })?;
         } else {
-            let item_matches_slot = match (&hand_item.kind, &data.kind) {
-                (ItemKind::Weapon, SlotKind::Weapon) => true,
-                (ItemKind::Compass, SlotKind::Compass) => true,
-                _ => false,
-            };
+            let player = server.player_store
+                .find_by(|p| p.id == self.player_id)
+                .ok_or("Player not found")?;
+
+            let attributes = server.player_attributes_store
+                .find_by(|attr| attr.player_id == self.player_id)
+                .ok_or("Player attributes not found")?;
 
-            if !item_matches_slot {
-                return Err("Item type doesn't match slot type".to_string());
+            hand_item.can_equip(&data.kind, player.level, &attributes)?;
+
+            if data.kind == SlotKind::Compass {
+                let has_active_expedition = server.expeditions_store
+                    .find_by(|e| e.participants.contains(&self.player_id) && e.ended_at.is_none())
+                    .is_some();
+
+                if has_active_expedition {
+                    return Err("Cannot change compass during active expedition".to_string());
+                }
             }
 
             if let Some(existing_item) = &target_slot.item {
@@ -198,19 +430,16 @@ impl MessageHandler {
             }
         }
 
-        if target_slot.is_equipment_slot() {
-            if let Some(current_stats) = server.player_stats_store.find_by(|s| s.player_id == self.player_id) {
-                let _ = current_stats.recalculate();
-            }
+        if (target_slot.is_equipment_slot() || target_slot.kind == SlotKind::Rune)
+            && let Some(current_stats) = server.player_stats_store.find_by(|s| s.player_id == self.player_id) {
+            let _ = current_stats.recalculate();
         }
 
-        let mut slots = server.slots_store.find_all_by(|slot| slot.player_id == self.player_id);
-        slots.sort_by_key(|slot| slot.index);
+        if target_slot.kind == SlotKind::Rune {
+            Runes::send(self.player_id).await;
+        }
 
-        Ok(vec![OutgoingMessage::new(
-            OutgoingEvent::Slots,
-            Box::new(slots) as Box<dyn erased_serde::Serialize + Send>,
-        )])
+        self.inventory_update()
     }
 
     async fn handle_send_chat_message(&self, data: SendChatMessage) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
@@ -234,6 +463,11 @@ impl MessageHandler {
             }
         }
 
+        let party = match data_kind {
+            ChatKind::Party => Some(Parties::of(self.player_id).ok_or("You are not in a party")?),
+            _ => None,
+        };
+
         let chat_message = ChatMessage::new(
             player.name.clone(),
             data.recipient,
@@ -244,7 +478,7 @@ impl MessageHandler {
         server.chat_store.insert(chat_message.clone())
             .map_err(|e| format!("Failed to store chat message: {}", e))?;
 
-        let msg = OutgoingMessage::new(OutgoingEvent::ChatMessage, Box::new(chat_message) as Box<dyn erased_serde::Serialize + Send>);
+        let msg = OutgoingMessage::new(OutgoingEvent::ChatMessage, Box::new(chat_message.clone()) as Box<dyn erased_serde::Serialize + Send>);
 
         match data_kind {
             ChatKind::General | ChatKind::Trade => {
@@ -255,6 +489,14 @@ impl MessageHandler {
                     ws_manager.send_to_player_by_name(recipient, msg).await;
                 }
             }
+            ChatKind::Party => {
+                for member in party.iter().flat_map(|party| party.members.iter()) {
+                    ws_manager.send_to_player(*member, OutgoingMessage::new(
+                        OutgoingEvent::ChatMessage,
+                        Box::new(chat_message.clone()) as Box<dyn erased_serde::Serialize + Send>,
+                    )).await;
+                }
+            }
         }
 
         Ok(vec![])
@@ -264,14 +506,15 @@ impl MessageHandler {
         let server = GameServer::global();
         let ws_manager = WebSocketManager::global();
 
-        let already_active = server.expeditions_store
-            .find_by(|e| e.participants.contains(&self.player_id) && e.ended_at.is_none())
-            .is_some();
+        let party = Parties::of(self.player_id);
 
-        if already_active {
-            return Err("Expedition already in progress".to_string());
+        if let Some(party) = &party
+            && !party.is_leader(self.player_id) {
+            return Err("Only the party leader can start a party expedition".to_string());
         }
 
+        ExpeditionService::ensure_ready(self.player_id)?;
+
         let mut compass_slot = server.slots_store
             .find_by(|slot| slot.player_id == self.player_id && slot.kind == SlotKind::Compass);
 
@@ -284,95 +527,870 @@ impl MessageHandler {
             return Err("Item in Compass slot is not a compass".to_string());
         }
 
-        let stats = item.stats.as_ref().ok_or("Compass has no stats")?;
-        let kind = stats.expedition_kind.clone().unwrap_or(ExpeditionKind::Hunt);
-
-        let player_resource = server.player_resource_store.find_by(|r| r.player_id == self.player_id).ok_or("Player resource not found")?;
-        if player_resource.energy <= 0 {
-            return Err("No energy to start expedition".to_string());
+        let kind = item.stats
+            .as_ref()
+            .and_then(|stats| stats.expedition_kind.clone())
+            .ok_or("Compass is not attuned to any expedition")?;
+
+        let mut participants = vec![self.player_id];
+
+        if let Some(party) = &party {
+            for member in party.members.iter().filter(|id| **id != self.player_id) {
+                match ExpeditionService::ensure_ready(*member) {
+                    Ok(()) => participants.push(*member),
+                    Err(reason) => {
+                        ws_manager.send_log_to_player(
+                            *member,
+                            format!("You could not join the party expedition: {}", reason),
+                        ).await;
+                    }
+                }
+            }
         }
 
-        let expedition = Expedition::new(vec![self.player_id], kind);
+        let difficulty = ExpeditionDifficulty::for_compass_level(item.level);
+
+        let expedition = Expedition::new(participants, kind, difficulty, item.template_id.clone());
 
         server.expeditions_store.insert(expedition.clone())
             .map_err(|e| format!("Failed to store expedition: {}", e))?;
 
-        ws_manager.send_log_to_player(self.player_id, "You started the expedition.".to_string()).await;
+        for participant in &expedition.participants {
+            ws_manager.send_log_to_player(
+                *participant,
+                format!("You started a {:?} {:?} expedition.", expedition.difficulty, expedition.kind),
+            ).await;
+        }
 
         Ok(vec![])
     }
 
     async fn handle_end_expedition(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
         let server = GameServer::global();
-        let ws_manager = WebSocketManager::global();
 
         let active = server.expeditions_store
             .find_by(|e| e.participants.contains(&self.player_id) && e.ended_at.is_none())
             .ok_or("No active expedition to end")?;
 
-        let _ = server.expeditions_store.update(&active.id, |exp| {
-            exp.ended_at = Some(Utc::now());
-        })?;
+        ExpeditionService::end(active.id, self.player_id, ExpeditionEndReason::Manual, "You left the expedition.").await?;
+
+        Ok(vec![])
+    }
+
+    async fn handle_toggle_loot(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        server.expeditions_store
+            .find_by(|e| e.participants.contains(&self.player_id) && e.ended_at.is_none())
+            .ok_or("No active expedition to loot in")?;
 
         let player_state = server.player_state_store
             .find_by(|state| state.player_id == self.player_id)
             .ok_or("Player state not found")?;
 
-        let updated_state = server.player_state_store.update(&player_state.id, |state| {
-            state.is_looting = false;
+        let updated_state: PlayerState = if player_state.is_looting {
+            ws_manager.send_log_to_player(self.player_id, "You stopped looting items".to_string()).await;
+            server.player_state_store.update(&player_state.id, |state| { state.is_looting = false; })?
+        } else {
+            ws_manager.send_log_to_player(self.player_id, "You started looting items".to_string()).await;
+            server.player_state_store.update(&player_state.id, |state| { state.is_looting = true; })?
+        };
+
+        Ok(vec![OutgoingMessage::new(
+            OutgoingEvent::PlayerState,
+            Box::new(updated_state) as Box<dyn erased_serde::Serialize + Send>,
+        )])
+    }
+
+    async fn handle_allocate_attributes(&self, data: AllocateAttributes) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let requested = [data.strength, data.dexterity, data.vitality, data.intelligence, data.spirit, data.luck]
+            .iter()
+            .try_fold(0u32, |total, points| total.checked_add(*points))
+            .ok_or("Too many attribute points requested")?;
+
+        if requested == 0 {
+            return Err("No attribute points to allocate".to_string());
+        }
+
+        let attributes = server.player_attributes_store
+            .find_by(|attr| attr.player_id == self.player_id)
+            .ok_or("Player attributes not found")?;
+
+        if requested > attributes.unspent_points {
+            return Err(format!(
+                "Not enough attribute points: {} requested, {} available",
+                requested, attributes.unspent_points
+            ));
+        }
+
+        let updated_attributes = server.player_attributes_store.update(&attributes.id, |attr| {
+            attr.strength += data.strength;
+            attr.dexterity += data.dexterity;
+            attr.vitality += data.vitality;
+            attr.intelligence += data.intelligence;
+            attr.spirit += data.spirit;
+            attr.luck += data.luck;
+            attr.unspent_points -= requested;
         })?;
 
-        let ground_slots = server.slots_store.find_all_by(|slot| {
-            slot.player_id == self.player_id && slot.kind == SlotKind::Ground
-        });
+        if let Some(current_stats) = server.player_stats_store.find_by(|s| s.player_id == self.player_id) {
+            current_stats.recalculate()?;
+        }
+
+        ws_manager.send_log_to_player(self.player_id, format!("You allocated {} attribute points.", requested)).await;
+
+        Ok(vec![OutgoingMessage::new(
+            OutgoingEvent::PlayerAttributes,
+            Box::new(updated_attributes) as Box<dyn erased_serde::Serialize + Send>,
+        )])
+    }
+
+    async fn handle_use_item(&self, data: UseItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        if !matches!(data.kind, SlotKind::Consumable | SlotKind::Inventory) {
+            return Err("Items can only be used from consumable or inventory slots".to_string());
+        }
+
+        let slot = server.slots_store
+            .find_by(|slot| {
+                slot.player_id == self.player_id
+                    && slot.kind == data.kind
+                    && slot.index == data.index
+            })
+            .ok_or("Slot not found")?;
+
+        let item = slot.item.as_ref().ok_or("No item in slot")?;
+
+        if !item.is_usable {
+            return Err(format!("{} cannot be used", item.name));
+        }
+
+        let effect = item.effect.clone().ok_or(format!("{} has no effect", item.name))?;
+
+        let resource = server.player_resource_store
+            .find_by(|r| r.player_id == self.player_id)
+            .ok_or("Player resource not found")?;
+
+        match &effect {
+            ItemEffect::RestoreEnergy { amount } => {
+                if resource.energy >= resource.max_energy {
+                    return Err("Energy is already full".to_string());
+                }
+
+                server.player_resource_store.update(&resource.id, |r| {
+                    r.energy = (r.energy + amount).min(r.max_energy);
+                })?;
+            }
+            ItemEffect::RestoreHp { amount } => {
+                if resource.hp >= resource.max_hp {
+                    return Err("HP is already full".to_string());
+                }
+
+                server.player_resource_store.update(&resource.id, |r| {
+                    r.hp = (r.hp + amount).min(r.max_hp);
+                })?;
+            }
+            ItemEffect::Buff { kind, value, duration_secs } => {
+                Buffs::apply(self.player_id, kind.clone(), *value, &item.template_id, *duration_secs)?;
+            }
+        }
+
+        let item_name = item.name.clone();
+        let item_id = item.id;
+
+        let updated_slot = server.slots_store.update(&slot.id, |slot| {
+            if let Some(item) = slot.item.as_mut() {
+                item.quantity -= 1;
+
+                if item.quantity == 0 {
+                    slot.item = None;
+                }
+            }
+        })?;
+
+        if updated_slot.item.is_none() {
+            let _ = server.items_store.remove(&item_id);
+        }
+
+        if matches!(effect, ItemEffect::Buff { .. }) {
+            Buffs::send(self.player_id).await;
+        }
+
+        ws_manager.send_log_to_player(self.player_id, format!("You used {}.", item_name)).await;
+
+        self.inventory_update()
+    }
+
+    async fn handle_enchant_item(&self, data: EnchantItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let attempt = Enchanting::enchant(self.player_id, data.kind, data.index, data.material)?;
+
+        let message = match attempt.outcome {
+            EnchantOutcome::Success => format!(
+                "The enchantment succeeded! {} is now +{}.",
+                attempt.item_name, attempt.from_level + 1
+            ),
+            EnchantOutcome::Failed => format!(
+                "The enchantment failed. {} is unchanged.",
+                attempt.item_name
+            ),
+            EnchantOutcome::Downgraded => format!(
+                "The enchantment failed and {} lost a level (+{}).",
+                attempt.item_name, attempt.from_level.saturating_sub(1)
+            ),
+            EnchantOutcome::Destroyed => format!(
+                "The enchantment failed and {} was destroyed!",
+                attempt.item_name
+            ),
+        };
+
+        ws_manager.send_log_to_player(self.player_id, message).await;
+
+        if attempt.outcome == EnchantOutcome::Destroyed
+            && let Some(current_stats) = server.player_stats_store.find_by(|s| s.player_id == self.player_id) {
+            let _ = current_stats.recalculate();
+        }
+
+        self.inventory_update()
+    }
+
+    async fn handle_upgrade_compass(&self, data: UpgradeCompass) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let (compass, upgraded) = CompassUpgrader::upgrade(self.player_id, data.kind, data.index)?;
+
+        ws_manager.send_log_to_player(
+            self.player_id,
+            format!(
+                "You upgraded your {} into a {}. It leads to {:?} expeditions.",
+                compass.name, upgraded.name, ExpeditionDifficulty::for_compass_level(upgraded.level)
+            ),
+        ).await;
+
+        self.inventory_update()
+    }
+
+    async fn handle_invite_to_party(&self, data: InviteToParty) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let (party, invitee_id) = Parties::invite(self.player_id, &data.player_name)?;
+        Parties::withdraw_invites(self.player_id).await;
+
+        let leader = server.player_store
+            .find_by(|p| p.id == self.player_id)
+            .ok_or("Player not found")?;
+
+        ws_manager.send_to_player(invitee_id, OutgoingMessage::new(
+            OutgoingEvent::PartyInvite,
+            Box::new(PartyInvite {
+                party_id: party.id,
+                leader_name: leader.name.clone(),
+            }) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_log_to_player(invitee_id, format!("{} invited you to their party.", leader.name)).await;
+
+        Parties::send(&party).await;
+        Parties::send_log(&party, format!("{} was invited to the party.", data.player_name)).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_accept_party_invite(&self, data: AcceptPartyInvite) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+
+        let party = Parties::accept(self.player_id, data.party_id)?;
+        Parties::withdraw_invites(self.player_id).await;
+
+        let player = server.player_store
+            .find_by(|p| p.id == self.player_id)
+            .ok_or("Player not found")?;
+
+        Parties::send(&party).await;
+        Parties::send_log(&party, format!("{} joined the party.", player.name)).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_decline_party_invite(&self, data: DeclinePartyInvite) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let party = Parties::decline(self.player_id, data.party_id)?;
+
+        let player = server.player_store
+            .find_by(|p| p.id == self.player_id)
+            .ok_or("Player not found")?;
+
+        Parties::send_log(&party, format!("{} declined the party invite.", player.name)).await;
+        Parties::settle(party).await;
+
+        ws_manager.send_log_to_player(self.player_id, "You declined the party invite.".to_string()).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_leave_party(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let party = Parties::of(self.player_id).ok_or("You are not in a party")?;
+
+        let player = server.player_store
+            .find_by(|p| p.id == self.player_id)
+            .ok_or("Player not found")?;
+
+        self.remove_from_party(&party, self.player_id, format!("{} left the party.", player.name)).await?;
+
+        ws_manager.send_log_to_player(self.player_id, "You left the party.".to_string()).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_kick_from_party(&self, data: KickFromParty) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let party = Parties::of(self.player_id).ok_or("You are not in a party")?;
+
+        if !party.is_leader(self.player_id) {
+            return Err("Only the party leader can kick members".to_string());
+        }
+
+        let target = server.player_store
+            .find_by(|p| p.name == data.player_name && party.members.contains(&p.id))
+            .ok_or(format!("{} is not in your party", data.player_name))?;
+
+        if target.id == self.player_id {
+            return Err("You cannot kick yourself. Leave the party instead.".to_string());
+        }
+
+        self.remove_from_party(&party, target.id, format!("{} was kicked from the party.", target.name)).await?;
 
-        for slot in ground_slots {
-            server.slots_store.update(&slot.id, |s| {
-                s.item = None;
-            })?;
+        ws_manager.send_log_to_player(target.id, "You were kicked from the party.".to_string()).await;
+
+        Ok(vec![])
+    }
+
+    async fn remove_from_party(&self, party: &Party, player_id: Uuid, message: String) -> Result<(), String> {
+        let ws_manager = WebSocketManager::global();
+
+        Parties::send_to(player_id, None).await;
+
+        match Parties::remove_member(party, player_id)? {
+            Some(updated) => {
+                Parties::send(&updated).await;
+                Parties::send_log(&updated, message).await;
+            }
+            None => {
+                for member in party.members.iter().filter(|id| **id != player_id) {
+                    Parties::send_to(*member, None).await;
+                    ws_manager.send_log_to_player(*member, format!("{} The party was disbanded.", message)).await;
+                }
+            }
         }
 
-        ws_manager.send_log_to_player(self.player_id, "You left the expedition.".to_string()).await;
+        Ok(())
+    }
+
+    async fn handle_set_party_loot_rule(&self, data: SetPartyLootRule) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let party = Parties::set_loot_rule(self.player_id, data.loot_rule)?;
+
+        Parties::send(&party).await;
+        Parties::send_log(&party, format!("The party loot rule is now {:?}.", party.loot_rule)).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_expedition_history(&self, data: ExpeditionHistory) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let history = ExpeditionService::history(self.player_id, data.page);
+
+        Ok(vec![OutgoingMessage::new(
+            OutgoingEvent::ExpeditionHistory,
+            Box::new(history) as Box<dyn erased_serde::Serialize + Send>,
+        )])
+    }
+
+    async fn handle_buy_item(&self, data: BuyItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let (item, cost) = Shop::buy(self.player_id, &data.template_id, data.quantity)?;
+
+        ws_manager.send_log_to_player(
+            self.player_id,
+            format!("You bought {} {} for {} cin.", item.quantity, item.name, cost),
+        ).await;
+
+        if ShopCatalog::global().get(&item.template_id).is_some_and(|listing| listing.stock.is_some()) {
+            Shop::broadcast_listings().await;
+        }
+
+        self.inventory_update()
+    }
+
+    async fn handle_sell_item(&self, data: SellItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let (item, price) = Shop::sell(self.player_id, data.kind, data.index, data.quantity)?;
+
+        ws_manager.send_log_to_player(
+            self.player_id,
+            format!("You sold {} {} for {} cin.", item.quantity, item.name, price),
+        ).await;
+
+        Shop::send_buybacks(self.player_id).await;
+
+        self.inventory_update()
+    }
+
+    async fn handle_buyback_item(&self, data: BuybackItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let buyback = Shop::buyback(self.player_id, data.buyback_id)?;
 
-        let mut all_slots = server.slots_store.find_all_by(|slot| slot.player_id == self.player_id);
-        all_slots.sort_by_key(|slot| slot.index);
+        ws_manager.send_log_to_player(
+            self.player_id,
+            format!("You bought back {} {} for {} cin.", buyback.item.quantity, buyback.item.name, buyback.price),
+        ).await;
+
+        Shop::send_buybacks(self.player_id).await;
+
+        self.inventory_update()
+    }
+
+    fn inventory_update(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+
+        let resource = Encumbrance::refresh(self.player_id)?;
+
+        let mut slots = server.slots_store.find_all_by(|slot| slot.player_id == self.player_id);
+        slots.sort_by_key(|slot| slot.index);
 
         Ok(vec![
             OutgoingMessage::new(
-                OutgoingEvent::PlayerState,
-                Box::new(updated_state) as Box<dyn erased_serde::Serialize + Send>,
+                OutgoingEvent::Slots,
+                Box::new(slots) as Box<dyn erased_serde::Serialize + Send>,
             ),
             OutgoingMessage::new(
-                OutgoingEvent::Slots,
-                Box::new(all_slots) as Box<dyn erased_serde::Serialize + Send>,
-            )
+                OutgoingEvent::PlayerResource,
+                Box::new(resource) as Box<dyn erased_serde::Serialize + Send>,
+            ),
         ])
     }
 
-    async fn handle_toggle_loot(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+    async fn handle_request_trade(&self, data: RequestTrade) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
         let server = GameServer::global();
         let ws_manager = WebSocketManager::global();
 
-        server.expeditions_store
-            .find_by(|e| e.participants.contains(&self.player_id) && e.ended_at.is_none())
-            .ok_or("No active expedition to loot in")?;
+        let (trade, partner_id) = Trades::request(self.player_id, &data.player_name)?;
 
-        let player_state = server.player_state_store
-            .find_by(|state| state.player_id == self.player_id)
-            .ok_or("Player state not found")?;
+        let requester = server.player_store
+            .find_by(|p| p.id == self.player_id)
+            .ok_or("Player not found")?;
 
-        let updated_state: PlayerState;
-        if player_state.is_looting {
-            updated_state = server.player_state_store.update(&player_state.id, |state| { state.is_looting = false; })?;
-            ws_manager.send_log_to_player(self.player_id, "You stopped looting items".to_string()).await;
-        } else {
-            updated_state = server.player_state_store.update(&player_state.id, |state| { state.is_looting = true; })?;
-            ws_manager.send_log_to_player(self.player_id, "You started looting items".to_string()).await;
+        ws_manager.send_to_player(partner_id, OutgoingMessage::new(
+            OutgoingEvent::TradeRequest,
+            Box::new(TradeRequest {
+                trade_id: trade.id,
+                requester_name: requester.name.clone(),
+            }) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_log_to_player(partner_id, format!("{} wants to trade with you.", requester.name)).await;
+        ws_manager.send_log_to_player(self.player_id, format!("You asked {} to trade.", data.player_name)).await;
+
+        Trades::send_to(self.player_id, Some(&trade)).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_accept_trade(&self, data: AcceptTrade) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let trade = Trades::accept(self.player_id, data.trade_id)?;
+
+        Trades::send(&trade).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_add_trade_item(&self, data: AddTradeItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let trade = Trades::add_item(self.player_id, data.kind, data.index)?;
+
+        Trades::send(&trade).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_remove_trade_item(&self, data: RemoveTradeItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let trade = Trades::remove_item(self.player_id, data.item_id)?;
+
+        Trades::send(&trade).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_set_trade_cin(&self, data: SetTradeCin) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let trade = Trades::set_cin(self.player_id, data.amount)?;
+
+        Trades::send(&trade).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_lock_trade(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let trade = Trades::lock(self.player_id)?;
+
+        Trades::send(&trade).await;
+
+        Ok(vec![])
+    }
+
+    async fn handle_confirm_trade(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let trade = Trades::confirm(self.player_id)?;
+
+        if !trade.both_confirmed() {
+            Trades::send(&trade).await;
+            return Ok(vec![]);
+        }
+
+        let participants = [trade.requester_id, trade.partner_id];
+
+        if let Err(e) = Trades::execute(&trade) {
+            if let Some(trade) = Trades::of(self.player_id) {
+                Trades::send(&trade).await;
+            }
+
+            for player_id in participants {
+                ws_manager.send_log_to_player(player_id, e.clone()).await;
+            }
+
+            return Ok(vec![]);
+        }
+
+        for player_id in participants {
+            Trades::send_to(player_id, None).await;
+            Trades::send_inventory(player_id).await;
+            ws_manager.send_log_to_player(player_id, "The trade was completed.".to_string()).await;
         }
 
+        Ok(vec![])
+    }
+
+    async fn handle_cancel_trade(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let trade = Trades::cancel(self.player_id)?;
+
+        for player_id in [trade.requester_id, trade.partner_id] {
+            Trades::send_to(player_id, None).await;
+            ws_manager.send_log_to_player(player_id, "The trade was cancelled.".to_string()).await;
+        }
+
+        Ok(vec![])
+    }
+
+    async fn handle_list_market_item(&self, data: ListMarketItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let listing = Market::list(self.player_id, data.kind, data.index, data.quantity, data.price, data.duration_hours)?;
+
+        ws_manager.send_log_to_player(
+            self.player_id,
+            format!(
+                "You listed {} {} for {} cin. A listing fee of {} cin was charged.",
+                listing.item.quantity, listing.item.name, listing.price, listing.fee
+            ),
+        ).await;
+
+        Market::send_own_listings(self.player_id).await;
+
+        self.inventory_update()
+    }
+
+    async fn handle_buy_market_listing(&self, data: BuyMarketListing) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let listing = Market::buy(self.player_id, data.listing_id)?;
+
+        let buyer = server.player_store
+            .find_by(|p| p.id == self.player_id)
+            .ok_or("Player not found")?;
+
+        ws_manager.send_log_to_player(
+            self.player_id,
+            format!("You bought {} {} for {} cin.", listing.item.quantity, listing.item.name, listing.price),
+        ).await;
+
+        Market::pay_seller(listing, &buyer.name).await?;
+
+        self.inventory_update()
+    }
+
+    async fn handle_cancel_market_listing(&self, data: CancelMarketListing) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let listing = Market::cancel(self.player_id, data.listing_id)?;
+
+        let body = format!(
+            "Your listing of {} {} was cancelled. The listing fee is not refunded.",
+            listing.item.quantity, listing.item.name
+        );
+
+        Market::return_to_seller(listing, "Listing cancelled", body).await?;
+
+        Ok(vec![])
+    }
+
+    async fn handle_search_market(&self, data: SearchMarket) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let filter = MarketFilter {
+            kind: data.kind,
+            tier: data.tier,
+            min_level: data.min_level,
+            max_level: data.max_level,
+        };
+
         Ok(vec![OutgoingMessage::new(
-            OutgoingEvent::PlayerState,
-            Box::new(updated_state) as Box<dyn erased_serde::Serialize + Send>,
+            OutgoingEvent::MarketListings,
+            Box::new(Market::search(&filter, data.page)) as Box<dyn erased_serde::Serialize + Send>,
+        )])
+    }
+
+    async fn handle_list_mail(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        Ok(vec![OutgoingMessage::new(
+            OutgoingEvent::Mailbox,
+            Box::new(Mailbox::list(self.player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )])
+    }
+
+    async fn handle_read_mail(&self, data: ReadMail) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let mail = Mailbox::read(self.player_id, data.mail_id)?;
+
+        Mailbox::send_list(self.player_id).await;
+
+        Ok(vec![OutgoingMessage::new(
+            OutgoingEvent::Mail,
+            Box::new(mail) as Box<dyn erased_serde::Serialize + Send>,
         )])
     }
-}
\ No newline at end of file
+
+    async fn handle_claim_attachments(&self, data: ClaimAttachments) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let mail = Mailbox::claim(self.player_id, data.mail_id)?;
+
+        let mut claimed: Vec<String> = mail.items
+            .iter()
+            .map(|item| format!("{} {}", item.quantity, item.name))
+            .collect();
+
+        if mail.cin > 0 {
+            claimed.push(format!("{} cin", mail.cin));
+        }
+
+        ws_manager.send_log_to_player(self.player_id, format!("You claimed {}.", claimed.join(", "))).await;
+
+        Mailbox::send_list(self.player_id).await;
+
+        self.inventory_update()
+    }
+
+    async fn handle_delete_mail(&self, data: DeleteMail) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        Mailbox::delete(self.player_id, data.mail_id)?;
+
+        self.handle_list_mail().await
+    }
+
+    async fn handle_craft(&self, data: Craft) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let result = Crafting::craft(self.player_id, &data.recipe_id, data.times).await?;
+
+        let mut messages = Vec::new();
+
+        for item in result.crafted.iter().chain(result.mailed.iter()) {
+            messages.push(format!("You crafted {} {}.", item.quantity, item.name));
+        }
+
+        if result.upgrades > 0 {
+            messages.push(format!("{} of your crafts turned out exceptionally well!", result.upgrades));
+        }
+
+        if result.failures > 0 {
+            messages.push(format!(
+                "{} of {} {} attempts failed and their materials were lost.",
+                result.failures, result.attempts, result.recipe_name
+            ));
+        }
+
+        if !result.mailed.is_empty() {
+            messages.push(format!(
+                "{} crafted items did not fit in your inventory and were sent to your mailbox.",
+                result.mailed.len()
+            ));
+        }
+
+        for message in messages {
+            ws_manager.send_log_to_player(self.player_id, message).await;
+        }
+
+        let mut responses = self.inventory_update()?;
+
+        responses.push(OutgoingMessage::new(
+            OutgoingEvent::CraftResult,
+            Box::new(result) as Box<dyn erased_serde::Serialize + Send>,
+        ));
+
+        Ok(responses)
+    }
+
+    async fn handle_salvage_item(&self, data: SalvageItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let record = Salvage::salvage(self.player_id, data.kind, data.index)?;
+
+        self.salvage_result(record).await
+    }
+
+    async fn handle_salvage_below_tier(&self, data: SalvageBelowTier) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let record = Salvage::salvage_below_tier(self.player_id, data.tier)?;
+
+        self.salvage_result(record).await
+    }
+
+    async fn salvage_result(&self, record: SalvageRecord) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let materials: Vec<String> = record.materials
+            .iter()
+            .map(|(template_id, quantity)| {
+                let name = ItemCatalog::global()
+                    .get(template_id)
+                    .map_or(template_id.as_str(), |template| template.name.as_str());
+
+                format!("{} {}", quantity, name)
+            })
+            .collect();
+
+        ws_manager.send_log_to_player(self.player_id, format!(
+            "You salvaged {} item(s) into {}.",
+            record.salvaged.len(),
+            materials.join(", ")
+        )).await;
+
+        let mut responses = self.inventory_update()?;
+
+        responses.push(OutgoingMessage::new(
+            OutgoingEvent::SalvageResult,
+            Box::new(record) as Box<dyn erased_serde::Serialize + Send>,
+        ));
+
+        Ok(responses)
+    }
+
+    async fn handle_undo_salvage(&self, data: UndoSalvage) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let record = Salvage::undo(self.player_id, data.salvage_id)?;
+
+        ws_manager.send_log_to_player(self.player_id, format!(
+            "Salvage undone: {} item(s) restored.",
+            record.salvaged.len()
+        )).await;
+
+        let mut responses = self.inventory_update()?;
+
+        responses.push(OutgoingMessage::new(
+            OutgoingEvent::SalvageUndone,
+            Box::new(record.id) as Box<dyn erased_serde::Serialize + Send>,
+        ));
+
+        Ok(responses)
+    }
+
+    async fn handle_split_stack(&self, data: SplitStack) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        Inventory::split(self.player_id, data.kind, data.index, data.quantity, data.target_index)?;
+
+        self.inventory_update()
+    }
+
+    async fn handle_move_item(&self, data: MoveItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        Inventory::move_item(self.player_id, data.from_kind, data.from_index, data.to_kind, data.to_index)?;
+
+        self.inventory_update()
+    }
+
+    async fn handle_sort_inventory(&self, data: SortInventory) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        Inventory::sort(self.player_id, data.by)?;
+
+        self.inventory_update()
+    }
+
+    async fn handle_compact_stacks(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let merged = Inventory::compact(self.player_id)?;
+
+        if merged > 0 {
+            ws_manager.send_log_to_player(self.player_id, format!("Merged {} partial stack(s).", merged)).await;
+        }
+
+        self.inventory_update()
+    }
+
+    async fn handle_buy_stash_tab(&self) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        let cost = Stash::buy_tab(self.player_id)?;
+
+        ws_manager.send_log_to_player(self.player_id, format!("You bought a new stash tab for {} cin.", cost)).await;
+
+        let mut responses = self.inventory_update()?;
+
+        responses.push(OutgoingMessage::new(
+            OutgoingEvent::StashInfo,
+            Box::new(Stash::info(self.player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        ));
+
+        Ok(responses)
+    }
+
+    async fn handle_destroy_item(&self, data: DestroyItem) -> Result<Vec<OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>>, String> {
+        let ws_manager = WebSocketManager::global();
+
+        match ItemDestroyer::destroy(self.player_id, data.kind, data.index, data.quantity, data.token)? {
+            DestroyOutcome::NeedsConfirmation(confirmation) => {
+                ws_manager.send_log_to_player(self.player_id, format!(
+                    "Confirm to destroy {} {}. This cannot be undone.",
+                    confirmation.quantity, confirmation.item_name
+                )).await;
+
+                Ok(vec![OutgoingMessage::new(
+                    OutgoingEvent::DestroyConfirmation,
+                    Box::new(confirmation) as Box<dyn erased_serde::Serialize + Send>,
+                )])
+            }
+            DestroyOutcome::Destroyed(audit) => {
+                ws_manager.send_log_to_player(self.player_id, format!(
+                    "You destroyed {} {}.",
+                    audit.quantity, audit.item_name
+                )).await;
+
+                let mut responses = self.inventory_update()?;
+
+                responses.push(OutgoingMessage::new(
+                    OutgoingEvent::ItemDestroyed,
+                    Box::new(audit) as Box<dyn erased_serde::Serialize + Send>,
+                ));
+
+                Ok(responses)
+            }
+        }
+    }
+}
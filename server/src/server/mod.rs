@@ -3,7 +3,7 @@ mod auth_routes;
 mod message_handler;
 mod websocket_manager;
 
-use crate::models::{ChatMessage, Expedition, Item, Player, PlayerAttributes, PlayerResource, PlayerState, PlayerStats, Slot};
+use crate::models::{Buff, Buyback, ChatMessage, DestroyConfirmation, EnchantAttempt, Expedition, Item, ItemAudit, Mail, MarketListing, Party, Player, PlayerAttributes, PlayerResource, PlayerState, PlayerStats, SalvageRecord, ShopStock, Slot, Trade};
 use crate::store::Store;
 use axum::http::{header, Method};
 use axum::response::IntoResponse;
@@ -24,11 +24,23 @@ pub struct GameServer {
     pub slots_store: Arc<Store<Slot>>,
     pub chat_store: Arc<Store<ChatMessage>>,
     pub expeditions_store: Arc<Store<Expedition>>,
+    pub buffs_store: Arc<Store<Buff>>,
+    pub enchant_attempts_store: Arc<Store<EnchantAttempt>>,
+    pub parties_store: Arc<Store<Party>>,
+    pub shop_stock_store: Arc<Store<ShopStock>>,
+    pub buybacks_store: Arc<Store<Buyback>>,
+    pub trades_store: Arc<Store<Trade>>,
+    pub market_listings_store: Arc<Store<MarketListing>>,
+    pub mail_store: Arc<Store<Mail>>,
+    pub salvage_records_store: Arc<Store<SalvageRecord>>,
+    pub item_audits_store: Arc<Store<ItemAudit>>,
+    pub destroy_confirmations_store: Arc<Store<DestroyConfirmation>>,
 }
 
 static GAME_SERVER: OnceCell<Arc<GameServer>> = OnceCell::new();
 
 impl GameServer {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         player_store: Store<Player>,
         player_resource_store: Store<PlayerResource>,
@@ -39,6 +51,17 @@ impl GameServer {
         slots_store: Store<Slot>,
         chat_store: Store<ChatMessage>,
         expeditions_store: Store<Expedition>,
+        buffs_store: Store<Buff>,
+        enchant_attempts_store: Store<EnchantAttempt>,
+        parties_store: Store<Party>,
+        shop_stock_store: Store<ShopStock>,
+        buybacks_store: Store<Buyback>,
+        trades_store: Store<Trade>,
+        market_listings_store: Store<MarketListing>,
+        mail_store: Store<Mail>,
+        salvage_records_store: Store<SalvageRecord>,
+        item_audits_store: Store<ItemAudit>,
+        destroy_confirmations_store: Store<DestroyConfirmation>,
     ) -> Self {
         WebSocketManager::initialize();
 
@@ -52,6 +75,17 @@ impl GameServer {
             slots_store: Arc::new(slots_store),
             chat_store: Arc::new(chat_store),
             expeditions_store: Arc::new(expeditions_store),
+            buffs_store: Arc::new(buffs_store),
+            enchant_attempts_store: Arc::new(enchant_attempts_store),
+            parties_store: Arc::new(parties_store),
+            shop_stock_store: Arc::new(shop_stock_store),
+            buybacks_store: Arc::new(buybacks_store),
+            trades_store: Arc::new(trades_store),
+            market_listings_store: Arc::new(market_listings_store),
+            mail_store: Arc::new(mail_store),
+            salvage_records_store: Arc::new(salvage_records_store),
+            item_audits_store: Arc::new(item_audits_store),
+            destroy_confirmations_store: Arc::new(destroy_confirmations_store),
         }
     }
 
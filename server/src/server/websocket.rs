@@ -1,9 +1,19 @@
 use crate::auth;
-use crate::messages::{IncomingMessage, OutgoingEvent, OutgoingMessage};
+use crate::messages::{IncomingMessage, OutgoingEvent, OutgoingMessage, PlayerInfo};
 use crate::meta::Meta;
 use crate::server::message_handler::MessageHandler;
 use crate::server::websocket_manager::WebSocketManager;
 use crate::server::GameServer;
+use crate::services::buffs::Buffs;
+use crate::services::encumbrance::Encumbrance;
+use crate::services::mailbox::Mailbox;
+use crate::services::market::Market;
+use crate::services::offline_progress::OfflineProgress;
+use crate::services::parties::Parties;
+use crate::services::runes::Runes;
+use crate::services::shop::Shop;
+use crate::services::stash::Stash;
+use crate::services::trades::Trades;
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{Query, State, WebSocketUpgrade};
 use axum::http::StatusCode;
@@ -43,6 +53,20 @@ async fn handle_socket(
     let (ws_sender, mut ws_receiver) = socket.split();
     let (tx, mut rx) = mpsc::unbounded_channel();
 
+    let offline_report = match OfflineProgress::catch_up(player_id).await {
+        Ok(report) => report,
+        Err(e) => {
+            println!("Failed to catch up offline progress for {}: {}", username, e);
+            None
+        }
+    };
+
+    if let Err(e) = Stash::ensure_slots(player_id) {
+        println!("Failed to create stash slots for {}: {}", username, e);
+    }
+
+    Trades::cancel_for(player_id, "The trade was cancelled.").await;
+
     let ws_manager = WebSocketManager::global();
     ws_manager.add_connection(player_id, username.clone(), tx);
 
@@ -57,6 +81,10 @@ async fn handle_socket(
 
     send_initial_data_to_user(player_id, &username).await;
 
+    if let Some(report) = offline_report {
+        OfflineProgress::send(player_id, report).await;
+    }
+
     let handler = MessageHandler::new(player_id);
 
     while let Some(msg) = ws_receiver.next().await {
@@ -81,7 +109,7 @@ async fn handle_socket(
                                 }
                             }
                             Err(e) => {
-                                ws_manager.send_log_to_player(player_id, format!("{}", e)).await;
+                                ws_manager.send_log_to_player(player_id, e).await;
                             }
                         }
                     }
@@ -100,6 +128,8 @@ async fn handle_socket(
     }
 
     ws_manager.remove_connection(&player_id, &username);
+    let _ = OfflineProgress::checkpoint(player_id);
+    Trades::cancel_for(player_id, &format!("{} disconnected. The trade was cancelled.", username)).await;
     ws_sender_task.abort();
 }
 
@@ -110,11 +140,11 @@ async fn send_initial_data_to_user(player_id: Uuid, username: &str) {
     ws_manager.send_log_to_player(player_id, format!("Welcome {}!", username)).await;
 
     if let Some(player) = server.player_store.find_by(|p| p.id == player_id) {
-        let msg = OutgoingMessage::new(OutgoingEvent::PlayerInfo, Box::new(player) as Box<dyn erased_serde::Serialize + Send>);
+        let msg = OutgoingMessage::new(OutgoingEvent::PlayerInfo, Box::new(PlayerInfo::new(player)) as Box<dyn erased_serde::Serialize + Send>);
         ws_manager.send_to_player(player_id, msg).await;
     }
 
-    if let Some(player_resource) = server.player_resource_store.find_by(|p| p.player_id == player_id) {
+    if let Ok(player_resource) = Encumbrance::refresh(player_id) {
         let msg = OutgoingMessage::new(OutgoingEvent::PlayerResource, Box::new(player_resource) as Box<dyn erased_serde::Serialize + Send>);
         ws_manager.send_to_player(player_id, msg).await;
     }
@@ -134,6 +164,15 @@ async fn send_initial_data_to_user(player_id: Uuid, username: &str) {
         ws_manager.send_to_player(player_id, msg).await;
     }
 
+    Buffs::send(player_id).await;
+    Runes::send(player_id).await;
+    Parties::send_to(player_id, Parties::of(player_id).as_ref()).await;
+    Shop::send_listings(player_id).await;
+    Shop::send_buybacks(player_id).await;
+    Market::send_own_listings(player_id).await;
+    Mailbox::send_list(player_id).await;
+    Stash::send(player_id).await;
+
     let mut slots = server.slots_store.find_all_by(|p| p.player_id == player_id);
     slots.sort_by_key(|p| p.index);
     let msg = OutgoingMessage::new(OutgoingEvent::Slots, Box::new(slots) as Box<dyn erased_serde::Serialize + Send>);
@@ -34,10 +34,9 @@ impl WebSocketManager {
     }
 
     pub async fn send_to_player(&self, player_id: Uuid, message: OutgoingMessage<Box<dyn erased_serde::Serialize + Send>>) {
-        if let Some(sender) = self.connections.get(&player_id) {
-            if let Ok(serialized) = serde_json::to_string(&message) {
-                let _ = sender.send(Message::Text(serialized));
-            }
+        if let Some(sender) = self.connections.get(&player_id)
+            && let Ok(serialized) = serde_json::to_string(&message) {
+            let _ = sender.send(Message::Text(serialized));
         }
     }
 
@@ -58,6 +57,10 @@ impl WebSocketManager {
     pub fn is_player_online(&self, player_name: &str) -> bool {
         self.player_names.contains_key(player_name)
     }
+
+    pub fn is_connected(&self, player_id: Uuid) -> bool {
+        self.connections.contains_key(&player_id)
+    }
 }
 
 static WEBSOCKET_MANAGER: once_cell::sync::OnceCell<WebSocketManager> = once_cell::sync::OnceCell::new();
@@ -0,0 +1,52 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage};
+use crate::models::{Buff, BuffKind};
+use crate::server::{GameServer, WebSocketManager};
+use uuid::Uuid;
+
+pub struct Buffs;
+
+impl Buffs {
+    pub fn active(player_id: Uuid) -> Vec<Buff> {
+        let server = GameServer::global();
+
+        server.buffs_store.find_all_by(|buff| buff.player_id == player_id && !buff.is_expired())
+    }
+
+    pub fn total(player_id: Uuid, kind: BuffKind) -> f32 {
+        Self::active(player_id)
+            .iter()
+            .filter(|buff| buff.kind == kind)
+            .map(|buff| buff.value)
+            .sum()
+    }
+
+    pub fn apply(player_id: Uuid, kind: BuffKind, value: f32, source: &str, duration_secs: u64) -> Result<Buff, String> {
+        let server = GameServer::global();
+
+        let existing = server.buffs_store
+            .find_all_by(|buff| buff.player_id == player_id && buff.kind == kind && buff.source == source);
+
+        for buff in existing {
+            server.buffs_store.remove(&buff.id)?;
+        }
+
+        let buff = Buff::new(player_id, kind.clone(), value, source.to_string(), duration_secs);
+        let buff = server.buffs_store.insert(buff)?;
+
+        if kind.affects_stats()
+            && let Some(stats) = server.player_stats_store.find_by(|s| s.player_id == player_id) {
+            stats.recalculate()?;
+        }
+
+        Ok(buff)
+    }
+
+    pub async fn send(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::PlayerBuffs,
+            Box::new(Self::active(player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+}
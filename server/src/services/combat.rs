@@ -0,0 +1,53 @@
+use crate::meta::{monsters, Monster};
+use crate::models::{Expedition, PlayerStats};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+pub const MONSTER_SPAWN_DELAY: Duration = Duration::from_secs(3);
+
+pub enum CombatState {
+    Idle { next_spawn: Instant },
+    Fighting(Encounter),
+}
+
+pub struct Encounter {
+    pub monster: Monster,
+    pub monster_hp: u64,
+    pub next_player_attack: Instant,
+    pub next_monster_attack: Instant,
+}
+
+pub struct Combat;
+
+impl Combat {
+    pub fn spawn(expedition: &Expedition, player_level: u8, stats: &PlayerStats, now: Instant) -> Option<Encounter> {
+        let max_level = player_level
+            .saturating_add(3)
+            .saturating_add(expedition.difficulty.monster_level_bonus());
+
+        let candidates: Vec<Monster> = monsters(&expedition.kind)
+            .into_iter()
+            .filter(|monster| monster.level <= max_level)
+            .collect();
+
+        let monster = candidates
+            .choose_weighted(&mut rand::thread_rng(), |monster| monster.spawn_weight)
+            .ok()?
+            .clone();
+
+        Some(Encounter {
+            monster_hp: monster.hp,
+            next_player_attack: now + Duration::from_millis(stats.attack_speed),
+            next_monster_attack: now + Duration::from_millis(monster.attack_speed),
+            monster,
+        })
+    }
+
+    pub fn damage(attack: u64, defense: u64) -> u64 {
+        let mitigated = attack as f64 * 100.0 / (100.0 + defense as f64);
+        let variance = rand::thread_rng().gen_range(0.85..=1.15);
+
+        ((mitigated * variance).round() as u64).max(1)
+    }
+}
@@ -0,0 +1,79 @@
+use crate::meta::compass_upgrade;
+use crate::models::{Item, ItemKind, SlotKind};
+use crate::server::GameServer;
+use crate::services::inventory::Inventory;
+use uuid::Uuid;
+
+pub struct CompassUpgrader;
+
+impl CompassUpgrader {
+    pub fn upgrade(player_id: Uuid, kind: SlotKind, index: u64) -> Result<(Item, Item), String> {
+        let server = GameServer::global();
+
+        if !matches!(kind, SlotKind::Inventory | SlotKind::Compass) {
+            return Err("Compasses can only be upgraded from inventory or compass slots".to_string());
+        }
+
+        let slot = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .ok_or("Slot not found")?;
+
+        let compass = slot.item.clone().ok_or("No item in slot")?;
+
+        if compass.kind != ItemKind::Compass {
+            return Err(format!("{} is not a compass", compass.name));
+        }
+
+        let upgrade = compass_upgrade(&compass.template_id)
+            .ok_or(format!("{} cannot be upgraded any further", compass.name))?;
+
+        let mut upgraded = Item::from_template(player_id, &upgrade.to_template_id, 1)?;
+        upgraded.enchanted = compass.enchanted;
+
+        if kind == SlotKind::Compass {
+            let has_active_expedition = server.expeditions_store
+                .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none())
+                .is_some();
+
+            if has_active_expedition {
+                return Err("Cannot upgrade compass during active expedition".to_string());
+            }
+
+            let player = server.player_store
+                .find_by(|p| p.id == player_id)
+                .ok_or("Player not found")?;
+
+            let attributes = server.player_attributes_store
+                .find_by(|attr| attr.player_id == player_id)
+                .ok_or("Player attributes not found")?;
+
+            upgraded.can_equip(&kind, player.level, &attributes)?;
+        }
+
+        let mut costs = upgrade.materials.clone();
+        costs.push(("cin".to_string(), upgrade.cin_cost));
+
+        for (template_id, quantity) in &costs {
+            let available = Inventory::count(player_id, template_id);
+
+            if available < *quantity {
+                return Err(format!("Not enough {}: {} needed, {} available", template_id, quantity, available));
+            }
+        }
+
+        for (template_id, quantity) in &costs {
+            Inventory::consume(player_id, template_id, *quantity)?;
+        }
+
+        let upgraded_clone = upgraded.clone();
+
+        server.slots_store.update(&slot.id, |slot| {
+            slot.item = Some(upgraded_clone);
+        })?;
+
+        let _ = server.items_store.remove(&compass.id);
+        server.items_store.insert(upgraded.clone())?;
+
+        Ok((compass, upgraded))
+    }
+}
@@ -0,0 +1,105 @@
+use crate::messages::CraftResult;
+use crate::meta::{ItemCatalog, RecipeBook, MAX_CRAFT_BATCH};
+use crate::models::{Item, SlotKind};
+use crate::server::GameServer;
+use crate::services::inventory::Inventory;
+use crate::services::mailbox::Mailbox;
+use uuid::Uuid;
+
+pub struct Crafting;
+
+impl Crafting {
+    pub async fn craft(player_id: Uuid, recipe_id: &str, times: u64) -> Result<CraftResult, String> {
+        let server = GameServer::global();
+
+        let recipe = RecipeBook::global()
+            .get(recipe_id)
+            .ok_or_else(|| format!("Unknown recipe '{}'", recipe_id))?;
+
+        if times == 0 || times > MAX_CRAFT_BATCH {
+            return Err(format!("You can craft between 1 and {} times at once", MAX_CRAFT_BATCH));
+        }
+
+        let mut missing = Vec::new();
+
+        for input in &recipe.inputs {
+            let needed = input.quantity * times;
+            let available = Inventory::count(player_id, &input.template_id);
+
+            if available < needed {
+                let name = ItemCatalog::global()
+                    .get(&input.template_id)
+                    .map_or(input.template_id.as_str(), |template| template.name.as_str());
+
+                missing.push(format!("{} {} (have {})", needed, name, available));
+            }
+        }
+
+        let cin_needed = recipe.cin_cost * times;
+        let cin_available = Inventory::count(player_id, "cin");
+
+        if cin_available < cin_needed {
+            missing.push(format!("{} cin (have {})", cin_needed, cin_available));
+        }
+
+        if !missing.is_empty() {
+            return Err(format!("Missing materials for {}: {}", recipe.name, missing.join(", ")));
+        }
+
+        for input in &recipe.inputs {
+            Inventory::consume(player_id, &input.template_id, input.quantity * times)?;
+        }
+
+        if cin_needed > 0 {
+            Inventory::consume(player_id, "cin", cin_needed)?;
+        }
+
+        let mut result = CraftResult {
+            recipe_id: recipe.id.clone(),
+            recipe_name: recipe.name.clone(),
+            attempts: times,
+            failures: 0,
+            upgrades: 0,
+            crafted: Vec::new(),
+            mailed: Vec::new(),
+        };
+
+        for _ in 0..times {
+            if rand::random::<f32>() >= recipe.success_chance {
+                result.failures += 1;
+                continue;
+            }
+
+            let output = match &recipe.upgrade {
+                Some(upgrade) if rand::random::<f32>() < recipe.upgrade_chance => {
+                    result.upgrades += 1;
+                    upgrade
+                }
+                _ => &recipe.output,
+            };
+
+            let item = Item::from_template(player_id, &output.template_id, output.quantity)?;
+
+            match item.add_to_empty_slot(SlotKind::Inventory) {
+                Ok(_) => {
+                    let _ = server.items_store.insert(item.clone());
+                    result.crafted.push(item);
+                }
+                Err(_) => result.mailed.push(item),
+            }
+        }
+
+        if !result.mailed.is_empty() {
+            Mailbox::send(
+                player_id,
+                "Crafting",
+                format!("{} results", recipe.name),
+                "Your inventory had no room for these crafted items.".to_string(),
+                result.mailed.clone(),
+                0,
+            ).await?;
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,120 @@
+use crate::meta::{enchant_cin_cost, enchant_failure_weights, enchant_success_chance, ENCHANT_SCROLL_TEMPLATE_ID, MAX_ENCHANT_LEVEL};
+use crate::models::{EnchantAttempt, EnchantMaterial, EnchantOutcome, SlotKind};
+use crate::server::GameServer;
+use crate::services::inventory::Inventory;
+use rand::Rng;
+use uuid::Uuid;
+
+pub struct Enchanting;
+
+impl Enchanting {
+    pub fn enchant(player_id: Uuid, kind: SlotKind, index: u64, material: EnchantMaterial) -> Result<EnchantAttempt, String> {
+        let server = GameServer::global();
+
+        if matches!(kind, SlotKind::Hand | SlotKind::Ground) {
+            return Err(format!("Items cannot be enchanted from a {:?} slot", kind));
+        }
+
+        let slot = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .ok_or("Slot not found")?;
+
+        let item = slot.item.clone().ok_or("No item in slot")?;
+
+        if !item.kind.is_enchantable() {
+            return Err(format!("{} cannot be enchanted", item.name));
+        }
+
+        if item.enchanted >= MAX_ENCHANT_LEVEL {
+            return Err(format!("{} is already at the maximum enchant level", item.name));
+        }
+
+        if kind == SlotKind::Compass {
+            let has_active_expedition = server.expeditions_store
+                .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none())
+                .is_some();
+
+            if has_active_expedition {
+                return Err("Cannot enchant compass during active expedition".to_string());
+            }
+        }
+
+        let cost = match material {
+            EnchantMaterial::Scroll => 1,
+            EnchantMaterial::Cin => enchant_cin_cost(item.enchanted),
+        };
+
+        let template_id = match material {
+            EnchantMaterial::Scroll => ENCHANT_SCROLL_TEMPLATE_ID,
+            EnchantMaterial::Cin => "cin",
+        };
+
+        Inventory::consume(player_id, template_id, cost)?;
+
+        let success_chance = enchant_success_chance(item.enchanted);
+
+        let outcome = if rand::random::<f32>() < success_chance {
+            EnchantOutcome::Success
+        } else {
+            Self::roll_failure(item.enchanted)
+        };
+
+        let to_level = match outcome {
+            EnchantOutcome::Success => Some(item.enchanted + 1),
+            EnchantOutcome::Failed => Some(item.enchanted),
+            EnchantOutcome::Downgraded => Some(item.enchanted.saturating_sub(1)),
+            EnchantOutcome::Destroyed => None,
+        };
+
+        match to_level {
+            Some(level) => {
+                server.slots_store.update(&slot.id, |slot| {
+                    if let Some(item) = slot.item.as_mut() {
+                        item.enchanted = level;
+                    }
+                })?;
+
+                let _ = server.items_store.update(&item.id, |item| item.enchanted = level);
+            }
+            None => {
+                server.slots_store.update(&slot.id, |slot| {
+                    slot.item = None;
+                })?;
+
+                let _ = server.items_store.remove(&item.id);
+            }
+        }
+
+        let attempt = EnchantAttempt::new(
+            player_id,
+            item.id,
+            item.template_id.clone(),
+            item.name.clone(),
+            material,
+            cost,
+            success_chance,
+            item.enchanted,
+            to_level,
+            outcome,
+        );
+
+        server.enchant_attempts_store.insert(attempt)
+    }
+
+    fn roll_failure(current_level: u32) -> EnchantOutcome {
+        let weights = enchant_failure_weights(current_level);
+        let total: u32 = weights.iter().map(|(_, weight)| weight).sum();
+
+        let mut roll = rand::thread_rng().gen_range(0..total.max(1));
+
+        for (outcome, weight) in weights {
+            if roll < weight {
+                return outcome;
+            }
+
+            roll -= weight;
+        }
+
+        EnchantOutcome::Failed
+    }
+}
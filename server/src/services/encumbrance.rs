@@ -0,0 +1,67 @@
+use crate::meta::{MAX_OVERWEIGHT_PERCENT, OVERWEIGHT_ENERGY_FACTOR};
+use crate::models::PlayerResource;
+use crate::server::GameServer;
+use uuid::Uuid;
+
+pub struct Encumbrance;
+
+impl Encumbrance {
+    pub fn carried_weight(player_id: Uuid) -> f32 {
+        let server = GameServer::global();
+
+        server.slots_store
+            .find_all_by(|slot| slot.player_id == player_id && slot.kind.counts_toward_weight())
+            .iter()
+            .filter_map(|slot| slot.item.as_ref())
+            .map(|item| item.total_weight())
+            .sum()
+    }
+
+    pub fn ensure_capacity(player_id: Uuid, additional_weight: f32, limit_percent: u64) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let resource = server.player_resource_store
+            .find_by(|r| r.player_id == player_id)
+            .ok_or("Player resource not found")?;
+
+        let limit = resource.weight_limit as f32 * limit_percent as f32 / 100.0;
+        let carried = Self::carried_weight(player_id);
+
+        if carried + additional_weight > limit {
+            return Err(format!(
+                "Too heavy to carry: {:.1} + {:.1} exceeds your limit of {:.1}",
+                carried, additional_weight, limit
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn refresh(player_id: Uuid) -> Result<PlayerResource, String> {
+        let server = GameServer::global();
+
+        let resource = server.player_resource_store
+            .find_by(|r| r.player_id == player_id)
+            .ok_or("Player resource not found")?;
+
+        let carried_weight = Self::carried_weight(player_id);
+
+        server.player_resource_store.update(&resource.id, |resource| {
+            resource.carried_weight = carried_weight;
+        })
+    }
+
+    pub fn energy_cost_multiplier(player_id: Uuid) -> f64 {
+        let server = GameServer::global();
+
+        let Some(resource) = server.player_resource_store.find_by(|r| r.player_id == player_id) else {
+            return 1.0;
+        };
+
+        let limit = resource.weight_limit.max(1) as f64;
+        let overweight = (Self::carried_weight(player_id) as f64 - limit).max(0.0);
+        let max_overweight = limit * (MAX_OVERWEIGHT_PERCENT as f64 - 100.0) / 100.0;
+
+        1.0 + (overweight.min(max_overweight) / limit) * OVERWEIGHT_ENERGY_FACTOR
+    }
+}
@@ -0,0 +1,263 @@
+use crate::messages::{ExpeditionHistoryEntry, ExpeditionHistoryPage, OutgoingEvent, OutgoingMessage, PlayerDefeated};
+use crate::meta::{ItemCatalog, Monster, DEATH_EXP_PENALTY_PERCENT, EXPEDITION_HISTORY_PAGE_SIZE, RESPAWN_DURATION_SECS};
+use crate::models::{ExpeditionEndReason, ExpeditionKind, ExpeditionSummary, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::encumbrance::Encumbrance;
+use crate::services::progression::Progression;
+use crate::services::runes::Runes;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+pub struct ExpeditionService;
+
+impl ExpeditionService {
+    pub fn ensure_ready(player_id: Uuid) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let already_active = server.expeditions_store
+            .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none())
+            .is_some();
+
+        if already_active {
+            return Err("Expedition already in progress".to_string());
+        }
+
+        let player_state = server.player_state_store
+            .find_by(|state| state.player_id == player_id)
+            .ok_or("Player state not found")?;
+
+        if let Some(respawn_at) = player_state.respawn_at {
+            let remaining = (respawn_at - Utc::now()).num_seconds().max(1);
+            return Err(format!("You are still recovering. Try again in {} seconds.", remaining));
+        }
+
+        let player_resource = server.player_resource_store
+            .find_by(|r| r.player_id == player_id)
+            .ok_or("Player resource not found")?;
+
+        if player_resource.energy == 0 {
+            return Err("No energy to start expedition".to_string());
+        }
+
+        if player_resource.hp == 0 {
+            return Err("You are too wounded to start an expedition".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub async fn end(
+        expedition_id: Uuid,
+        player_id: Uuid,
+        reason: ExpeditionEndReason,
+        message: &str,
+    ) -> Result<(), String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        server.expeditions_store.update(&expedition_id, |exp| {
+            exp.participants.retain(|id| *id != player_id);
+
+            if let Some(summary) = exp.summary_mut(player_id) {
+                summary.end_reason = Some(reason);
+                summary.ended_at = Some(Utc::now());
+            }
+
+            if exp.participants.is_empty() {
+                exp.ended_at = Some(Utc::now());
+            }
+        })?;
+
+        let ground_slots = server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id && slot.kind == SlotKind::Ground
+        });
+
+        for slot in ground_slots {
+            server.slots_store.update(&slot.id, |s| {
+                s.item = None;
+            })?;
+        }
+
+        if let Some(player_state) = server.player_state_store.find_by(|state| state.player_id == player_id) {
+            let updated_state = server.player_state_store.update(&player_state.id, |state| {
+                state.is_looting = false;
+            })?;
+
+            ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                OutgoingEvent::PlayerState,
+                Box::new(updated_state) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+        }
+
+        let mut all_slots = server.slots_store.find_all_by(|slot| slot.player_id == player_id);
+        all_slots.sort_by_key(|slot| slot.index);
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::Slots,
+            Box::new(all_slots) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::ExpeditionCountup,
+            Box::new(-1) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_log_to_player(player_id, message.to_string()).await;
+
+        Ok(())
+    }
+
+    pub async fn handle_defeat(expedition_id: Uuid, player_id: Uuid, monster: &Monster) -> Result<(), String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        Self::end(
+            expedition_id,
+            player_id,
+            ExpeditionEndReason::Death,
+            &format!("You were defeated by the {}. Everything left on the ground is lost.", monster.name),
+        ).await?;
+
+        let exp_lost = Progression::lose_exp_percent(player_id, DEATH_EXP_PENALTY_PERCENT).await?;
+
+        let player_state = server.player_state_store
+            .find_by(|state| state.player_id == player_id)
+            .ok_or("Player state not found")?;
+
+        let updated_state = server.player_state_store.update(&player_state.id, |state| {
+            state.respawn_at = Some(Utc::now() + Duration::seconds(RESPAWN_DURATION_SECS));
+        })?;
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::PlayerDefeated,
+            Box::new(PlayerDefeated {
+                monster_id: monster.id.clone(),
+                name: monster.name.clone(),
+                exp_lost,
+                respawn_at: updated_state.respawn_at,
+            }) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::PlayerState,
+            Box::new(updated_state) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_log_to_player(
+            player_id,
+            format!("You lost {} experience and must recover for {} seconds.", exp_lost, RESPAWN_DURATION_SECS),
+        ).await;
+
+        Ok(())
+    }
+
+    pub fn record(player_id: Uuid, f: impl FnOnce(&mut ExpeditionSummary)) {
+        let server = GameServer::global();
+
+        let Some(expedition) = server.expeditions_store
+            .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none()) else {
+            return;
+        };
+
+        let _ = server.expeditions_store.update(&expedition.id, |exp| {
+            if let Some(summary) = exp.summary_mut(player_id) {
+                f(summary);
+            }
+        });
+    }
+
+    pub fn history(player_id: Uuid, page: usize) -> ExpeditionHistoryPage {
+        let server = GameServer::global();
+        let catalog = ItemCatalog::global();
+
+        let mut expeditions = server.expeditions_store.find_all_by(|e| {
+            e.summary(player_id).is_some_and(|summary| summary.ended_at.is_some())
+        });
+
+        expeditions.sort_by_key(|e| std::cmp::Reverse(e.summary(player_id).and_then(|summary| summary.ended_at)));
+
+        let total = expeditions.len();
+        let total_pages = total.div_ceil(EXPEDITION_HISTORY_PAGE_SIZE).max(1);
+        let page = page.clamp(1, total_pages);
+
+        let entries = expeditions
+            .into_iter()
+            .skip((page - 1) * EXPEDITION_HISTORY_PAGE_SIZE)
+            .take(EXPEDITION_HISTORY_PAGE_SIZE)
+            .filter_map(|expedition| {
+                let summary = expedition.summary(player_id)?.clone();
+                let ended_at = summary.ended_at?;
+                let duration_secs = (ended_at - expedition.started_at).num_seconds().max(0) as u64;
+                let per_hour = |amount: u64| amount as f64 * 3600.0 / duration_secs.max(1) as f64;
+
+                Some(ExpeditionHistoryEntry {
+                    id: expedition.id,
+                    kind: expedition.kind.clone(),
+                    difficulty: expedition.difficulty,
+                    compass_name: catalog
+                        .get(&expedition.compass_template_id)
+                        .map(|template| template.name.clone())
+                        .unwrap_or_else(|| expedition.compass_template_id.clone()),
+                    compass_template_id: expedition.compass_template_id.clone(),
+                    party_size: expedition.summaries.len(),
+                    started_at: expedition.started_at,
+                    ended_at,
+                    duration_secs,
+                    exp_per_hour: per_hour(summary.exp),
+                    cin_per_hour: per_hour(summary.cin),
+                    exp: summary.exp,
+                    cin: summary.cin,
+                    items_found: summary.items_found,
+                    energy_spent: summary.energy_spent,
+                    end_reason: summary.end_reason,
+                })
+            })
+            .collect();
+
+        ExpeditionHistoryPage {
+            page,
+            total_pages,
+            total,
+            entries,
+        }
+    }
+
+    pub fn energy_cost(player_id: Uuid, kind: &ExpeditionKind) -> u64 {
+        let server = GameServer::global();
+
+        let (base_cost, level_factor, enchant_factor) = match kind {
+            ExpeditionKind::Hunt => (4u64, 0.5, 0.3),
+            ExpeditionKind::Gathering => (3u64, 0.4, 0.2),
+            ExpeditionKind::Mining => (6u64, 0.7, 0.4),
+            ExpeditionKind::Fishing => (2u64, 0.3, 0.2),
+        };
+
+        let compass_slot = server.slots_store
+            .find_by(|slot| {
+                slot.player_id == player_id
+                    && slot.kind == SlotKind::Compass
+                    && slot.item.is_some()
+            });
+
+        if let Some(slot) = compass_slot
+            && let Some(compass) = &slot.item {
+            let level_cost = (compass.level as f64 * level_factor).round() as u64;
+
+            let enchant_cost = (compass.enchanted as f64 * enchant_factor).round() as u64;
+
+            let total_cost = base_cost + level_cost + enchant_cost;
+
+            return Self::apply_energy_modifiers(player_id, total_cost.max(base_cost));
+        }
+
+        Self::apply_energy_modifiers(player_id, base_cost)
+    }
+
+    fn apply_energy_modifiers(player_id: Uuid, energy_cost: u64) -> u64 {
+        let rune_reduction = (Runes::totals(player_id).energy_cost_reduction as f64).clamp(0.0, 0.5);
+
+        let energy_cost = energy_cost as f64 * (1.0 - rune_reduction);
+
+        (energy_cost * Encumbrance::energy_cost_multiplier(player_id)).round().max(1.0) as u64
+    }
+}
@@ -0,0 +1,311 @@
+use crate::messages::InventorySort;
+use crate::meta::ItemCatalog;
+use crate::models::{Item, Slot, SlotKind};
+use crate::server::GameServer;
+use std::cmp::Reverse;
+use uuid::Uuid;
+
+pub struct Inventory;
+
+impl Inventory {
+    fn stacks(player_id: Uuid, template_id: &str) -> Vec<Slot> {
+        let server = GameServer::global();
+
+        let mut slots = server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id
+                && matches!(slot.kind, SlotKind::Inventory | SlotKind::Consumable)
+                && slot.item.as_ref().is_some_and(|item| item.template_id == template_id)
+        });
+
+        slots.sort_by_key(|slot| (slot.kind != SlotKind::Inventory, slot.index));
+
+        slots
+    }
+
+    pub fn count(player_id: Uuid, template_id: &str) -> u64 {
+        Self::stacks(player_id, template_id)
+            .iter()
+            .filter_map(|slot| slot.item.as_ref())
+            .map(|item| item.quantity)
+            .sum()
+    }
+
+    pub fn consume(player_id: Uuid, template_id: &str, quantity: u64) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let available = Self::count(player_id, template_id);
+
+        if available < quantity {
+            let name = ItemCatalog::global()
+                .get(template_id)
+                .map_or(template_id, |template| template.name.as_str());
+
+            return Err(format!("Not enough {}: {} needed, {} available", name, quantity, available));
+        }
+
+        let mut remaining = quantity;
+
+        for slot in Self::stacks(player_id, template_id) {
+            if remaining == 0 {
+                break;
+            }
+
+            let Some(item) = &slot.item else {
+                continue;
+            };
+
+            let taken = item.quantity.min(remaining);
+            remaining -= taken;
+
+            let updated = server.slots_store.update(&slot.id, |slot| {
+                if let Some(item) = slot.item.as_mut() {
+                    item.quantity -= taken;
+
+                    if item.quantity == 0 {
+                        slot.item = None;
+                    }
+                }
+            })?;
+
+            if updated.item.is_none() {
+                let _ = server.items_store.remove(&item.id);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn credit(player_id: Uuid, template_id: &str, quantity: u64) -> Result<Item, String> {
+        let server = GameServer::global();
+
+        let item = Item::from_template(player_id, template_id, quantity)?;
+
+        item.add_to_empty_slot(SlotKind::Inventory)?;
+        let _ = server.items_store.insert(item.clone());
+
+        Ok(item)
+    }
+
+    pub fn snapshot(player_id: Uuid) -> Vec<Slot> {
+        let server = GameServer::global();
+
+        server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id && matches!(slot.kind, SlotKind::Inventory | SlotKind::Consumable)
+        })
+    }
+
+    pub fn restore(snapshot: Vec<Slot>) {
+        let server = GameServer::global();
+
+        for slot in snapshot {
+            let _ = server.slots_store.update(&slot.id, |current| {
+                *current = slot.clone();
+            });
+
+            if let Some(item) = slot.item {
+                let _ = server.items_store.insert(item);
+            }
+        }
+    }
+
+    fn slot(player_id: Uuid, kind: &SlotKind, index: u64) -> Result<Slot, String> {
+        let server = GameServer::global();
+
+        server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == *kind && slot.index == index)
+            .ok_or("Slot not found".to_string())
+    }
+
+    fn ensure_fits(item: &Item, kind: &SlotKind) -> Result<(), String> {
+        match kind {
+            SlotKind::Inventory => Ok(()),
+            SlotKind::Consumable if item.kind.slot_kind() == Some(SlotKind::Consumable) => Ok(()),
+            _ => Err(format!("{} cannot be placed in a {:?} slot", item.name, kind)),
+        }
+    }
+
+    pub fn split(player_id: Uuid, kind: SlotKind, index: u64, quantity: u64, target_index: Option<u64>) -> Result<Item, String> {
+        let server = GameServer::global();
+
+        if !matches!(kind, SlotKind::Inventory | SlotKind::Consumable) {
+            return Err(format!("Stacks cannot be split from a {:?} slot", kind));
+        }
+
+        let source = Self::slot(player_id, &kind, index)?;
+        let item = source.item.clone().ok_or("No item in slot")?;
+
+        if quantity == 0 || quantity >= item.quantity {
+            return Err(format!("You can split between 1 and {} {}", item.quantity.saturating_sub(1), item.name));
+        }
+
+        let target = match target_index {
+            Some(target_index) => Self::slot(player_id, &kind, target_index)?,
+            None => Self::slot(player_id, &SlotKind::Hand, 0)?,
+        };
+
+        if target.item.is_some() {
+            return Err("The target slot is not empty".to_string());
+        }
+
+        let split = Item {
+            id: Uuid::new_v4(),
+            quantity,
+            ..item
+        };
+
+        server.slots_store.update(&source.id, |slot| {
+            if let Some(item) = slot.item.as_mut() {
+                item.quantity -= quantity;
+            }
+        })?;
+
+        server.slots_store.update(&target.id, |slot| {
+            slot.item = Some(split.clone());
+        })?;
+
+        server.items_store.insert(split)
+    }
+
+    pub fn move_item(player_id: Uuid, from_kind: SlotKind, from_index: u64, to_kind: SlotKind, to_index: u64) -> Result<(), String> {
+        let server = GameServer::global();
+
+        if !matches!(from_kind, SlotKind::Inventory | SlotKind::Consumable) {
+            return Err(format!("Items cannot be moved from a {:?} slot", from_kind));
+        }
+
+        let source = Self::slot(player_id, &from_kind, from_index)?;
+        let target = Self::slot(player_id, &to_kind, to_index)?;
+
+        if source.id == target.id {
+            return Ok(());
+        }
+
+        let item = source.item.clone().ok_or("No item in slot")?;
+        Self::ensure_fits(&item, &to_kind)?;
+
+        match &target.item {
+            Some(existing) if existing.stacks_with(&item) => {
+                server.slots_store.update(&target.id, |slot| {
+                    if let Some(existing) = slot.item.as_mut() {
+                        existing.quantity += item.quantity;
+                    }
+                })?;
+
+                server.slots_store.update(&source.id, |slot| {
+                    slot.item = None;
+                })?;
+
+                let _ = server.items_store.remove(&item.id);
+            }
+            Some(existing) => {
+                Self::ensure_fits(existing, &from_kind)?;
+
+                let existing = existing.clone();
+
+                server.slots_store.update(&target.id, |slot| {
+                    slot.item = Some(item);
+                })?;
+
+                server.slots_store.update(&source.id, |slot| {
+                    slot.item = Some(existing);
+                })?;
+            }
+            None => {
+                server.slots_store.update(&target.id, |slot| {
+                    slot.item = Some(item);
+                })?;
+
+                server.slots_store.update(&source.id, |slot| {
+                    slot.item = None;
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn inventory_slots(player_id: Uuid) -> Vec<Slot> {
+        let server = GameServer::global();
+
+        let mut slots = server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id && slot.kind == SlotKind::Inventory
+        });
+
+        slots.sort_by_key(|slot| slot.index);
+
+        slots
+    }
+
+    pub fn compact(player_id: Uuid) -> Result<u64, String> {
+        let server = GameServer::global();
+
+        let mut stacks: Vec<(Uuid, Item)> = Vec::new();
+        let mut merged = 0;
+
+        for slot in Self::inventory_slots(player_id) {
+            let Some(item) = slot.item else {
+                continue;
+            };
+
+            match stacks.iter_mut().find(|(_, stack)| stack.stacks_with(&item)) {
+                Some((stack_slot_id, stack)) => {
+                    stack.quantity += item.quantity;
+
+                    server.slots_store.update(stack_slot_id, |slot| {
+                        if let Some(existing) = slot.item.as_mut() {
+                            existing.quantity += item.quantity;
+                        }
+                    })?;
+
+                    server.slots_store.update(&slot.id, |slot| {
+                        slot.item = None;
+                    })?;
+
+                    let _ = server.items_store.remove(&item.id);
+                    merged += 1;
+                }
+                None => stacks.push((slot.id, item)),
+            }
+        }
+
+        Ok(merged)
+    }
+
+    pub fn sort(player_id: Uuid, by: InventorySort) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let slots = Self::inventory_slots(player_id);
+
+        let mut items: Vec<Item> = slots
+            .iter()
+            .filter_map(|slot| slot.item.clone())
+            .collect();
+
+        match by {
+            InventorySort::Kind => items.sort_by(|a, b| {
+                (&a.kind, Reverse(&a.tier), &a.name, Reverse(a.level), Reverse(a.enchanted))
+                    .cmp(&(&b.kind, Reverse(&b.tier), &b.name, Reverse(b.level), Reverse(b.enchanted)))
+            }),
+            InventorySort::Tier => items.sort_by(|a, b| {
+                (Reverse(&a.tier), &a.kind, &a.name, Reverse(a.level), Reverse(a.enchanted))
+                    .cmp(&(Reverse(&b.tier), &b.kind, &b.name, Reverse(b.level), Reverse(b.enchanted)))
+            }),
+            InventorySort::Name => items.sort_by(|a, b| {
+                (&a.name, Reverse(a.level), Reverse(a.enchanted))
+                    .cmp(&(&b.name, Reverse(b.level), Reverse(b.enchanted)))
+            }),
+        }
+
+        let mut items = items.into_iter();
+
+        for slot in slots {
+            let item = items.next();
+
+            server.slots_store.update(&slot.id, |slot| {
+                slot.item = item;
+            })?;
+        }
+
+        Ok(())
+    }
+}
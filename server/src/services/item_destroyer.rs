@@ -0,0 +1,108 @@
+use crate::meta::DESTROY_CONFIRM_SECS;
+use crate::models::{DestroyConfirmation, ItemAudit, ItemAuditAction, ItemTier, SlotKind};
+use crate::server::GameServer;
+use crate::services::stash::Stash;
+use uuid::Uuid;
+
+pub enum DestroyOutcome {
+    NeedsConfirmation(DestroyConfirmation),
+    Destroyed(ItemAudit),
+}
+
+pub struct ItemDestroyer;
+
+impl ItemDestroyer {
+    pub fn destroy(
+        player_id: Uuid,
+        kind: SlotKind,
+        index: u64,
+        quantity: Option<u64>,
+        token: Option<Uuid>,
+    ) -> Result<DestroyOutcome, String> {
+        let server = GameServer::global();
+
+        if !matches!(kind, SlotKind::Inventory | SlotKind::Consumable | SlotKind::Hand | SlotKind::Ground | SlotKind::Stash) {
+            return Err(format!("Items cannot be destroyed from a {:?} slot", kind));
+        }
+
+        if kind == SlotKind::Stash {
+            Stash::ensure_accessible(player_id)?;
+        }
+
+        let slot = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .ok_or("Slot not found")?;
+
+        let item = slot.item.clone().ok_or("No item in slot")?;
+
+        let quantity = quantity.unwrap_or(item.quantity);
+
+        if quantity == 0 || quantity > item.quantity {
+            return Err(format!("You can destroy between 1 and {} {}", item.quantity, item.name));
+        }
+
+        if item.tier >= ItemTier::Rare {
+            let Some(token) = token else {
+                let confirmation = server.destroy_confirmations_store.insert(DestroyConfirmation::new(
+                    player_id,
+                    item.id,
+                    item.name.clone(),
+                    quantity,
+                    DESTROY_CONFIRM_SECS,
+                ))?;
+
+                return Ok(DestroyOutcome::NeedsConfirmation(confirmation));
+            };
+
+            let confirmation = server.destroy_confirmations_store
+                .find_by(|c| c.id == token && c.player_id == player_id)
+                .ok_or("Invalid confirmation token")?;
+
+            let _ = server.destroy_confirmations_store.remove(&confirmation.id);
+
+            if confirmation.is_expired() {
+                return Err("The confirmation has expired, please try again".to_string());
+            }
+
+            if confirmation.item_id != item.id || confirmation.quantity != quantity {
+                return Err("The confirmation does not match this item".to_string());
+            }
+        }
+
+        let whole_stack = quantity == item.quantity;
+
+        server.slots_store.update(&slot.id, |slot| {
+            if whole_stack {
+                slot.item = None;
+            } else if let Some(item) = slot.item.as_mut() {
+                item.quantity -= quantity;
+            }
+        })?;
+
+        if whole_stack {
+            let _ = server.items_store.remove(&item.id);
+        } else {
+            let _ = server.items_store.update(&item.id, |item| {
+                item.quantity -= quantity;
+            });
+        }
+
+        let audit = server.item_audits_store.insert(ItemAudit::new(
+            player_id,
+            &item,
+            quantity,
+            kind,
+            ItemAuditAction::Destroyed,
+        ))?;
+
+        Ok(DestroyOutcome::Destroyed(audit))
+    }
+
+    pub fn expire_confirmations() {
+        let server = GameServer::global();
+
+        for confirmation in server.destroy_confirmations_store.find_all_by(|c| c.is_expired()) {
+            let _ = server.destroy_confirmations_store.remove(&confirmation.id);
+        }
+    }
+}
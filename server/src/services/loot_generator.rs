@@ -0,0 +1,61 @@
+use crate::meta::{loot_table, rare_find_table, ItemCatalog, ItemTemplate, LootEntry, LootTable};
+use crate::models::{ExpeditionDifficulty, ExpeditionKind, Item};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use uuid::Uuid;
+
+pub struct LootGenerator;
+
+impl LootGenerator {
+    pub fn roll(player_id: Uuid, kind: &ExpeditionKind, difficulty: ExpeditionDifficulty) -> Option<Item> {
+        Self::roll_table(player_id, loot_table(kind, difficulty))
+    }
+
+    pub fn roll_rare_find(player_id: Uuid, kind: &ExpeditionKind) -> Option<Item> {
+        Self::roll_table(player_id, rare_find_table(kind))
+    }
+
+    fn roll_table(player_id: Uuid, table: LootTable) -> Option<Item> {
+        let (entry, template) = Self::pick_entry(table)?;
+
+        let mut rng = rand::thread_rng();
+        let quantity = rng.gen_range(entry.min_quantity..=entry.max_quantity.max(entry.min_quantity));
+
+        Some(template.create(player_id, quantity.max(1)))
+    }
+
+    fn pick_entry(table: LootTable) -> Option<(LootEntry, &'static ItemTemplate)> {
+        let catalog = ItemCatalog::global();
+        let mut rng = rand::thread_rng();
+
+        let entries: Vec<(LootEntry, &ItemTemplate)> = table.entries
+            .into_iter()
+            .filter(|entry| entry.weight > 0)
+            .filter_map(|entry| {
+                let template = catalog.get(&entry.template_id)?;
+                Some((entry, template))
+            })
+            .collect();
+
+        let available_tiers: Vec<_> = table.tier_weights
+            .iter()
+            .filter(|(tier, weight)| {
+                *weight > 0 && entries.iter().any(|(_, template)| template.tier == *tier)
+            })
+            .collect();
+
+        let (tier, _) = available_tiers
+            .choose_weighted(&mut rng, |(_, weight)| *weight)
+            .ok()?;
+
+        let tier_entries: Vec<&(LootEntry, &ItemTemplate)> = entries
+            .iter()
+            .filter(|(_, template)| template.tier == *tier)
+            .collect();
+
+        tier_entries
+            .choose_weighted(&mut rng, |(entry, _)| entry.weight)
+            .ok()
+            .map(|(entry, template)| (entry.clone(), *template))
+    }
+}
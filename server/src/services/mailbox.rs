@@ -0,0 +1,134 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage};
+use crate::models::{Item, Mail, Slot, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use uuid::Uuid;
+
+pub struct Mailbox;
+
+impl Mailbox {
+    pub async fn send(
+        player_id: Uuid,
+        sender: &str,
+        subject: String,
+        body: String,
+        items: Vec<Item>,
+        cin: u64,
+    ) -> Result<Mail, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let mail = server.mail_store.insert(Mail::new(player_id, sender, subject, body, items, cin))?;
+
+        ws_manager.send_log_to_player(player_id, format!("You have new mail: {}", mail.subject)).await;
+        Self::send_list(player_id).await;
+
+        Ok(mail)
+    }
+
+    pub fn list(player_id: Uuid) -> Vec<Mail> {
+        let server = GameServer::global();
+
+        let mut mail = server.mail_store.find_all_by(|mail| mail.player_id == player_id);
+        mail.sort_by_key(|mail| std::cmp::Reverse(mail.created_at));
+
+        mail
+    }
+
+    fn find(player_id: Uuid, mail_id: Uuid) -> Result<Mail, String> {
+        let server = GameServer::global();
+
+        server.mail_store
+            .find_by(|mail| mail.id == mail_id && mail.player_id == player_id)
+            .ok_or("Mail not found".to_string())
+    }
+
+    pub fn read(player_id: Uuid, mail_id: Uuid) -> Result<Mail, String> {
+        let server = GameServer::global();
+
+        let mail = Self::find(player_id, mail_id)?;
+
+        server.mail_store.update(&mail.id, |mail| {
+            mail.read = true;
+        })
+    }
+
+    pub fn claim(player_id: Uuid, mail_id: Uuid) -> Result<Mail, String> {
+        let server = GameServer::global();
+
+        let mail = Self::find(player_id, mail_id)?;
+
+        let mut claimed = None;
+
+        server.mail_store.update(&mail.id, |mail| {
+            if mail.has_attachments() {
+                claimed = Some(mail.clone());
+
+                mail.items.clear();
+                mail.cin = 0;
+                mail.read = true;
+            }
+        })?;
+
+        let mail = claimed.ok_or("This mail has no attachments")?;
+
+        let snapshot: Vec<Slot> = server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id && slot.kind == SlotKind::Inventory
+        });
+
+        let Ok(attachments) = Self::deliver(player_id, &mail) else {
+            for slot in snapshot {
+                let _ = server.slots_store.update(&slot.id, |current| {
+                    *current = slot.clone();
+                });
+            }
+
+            server.mail_store.update(&mail.id, |current| {
+                current.items = mail.items.clone();
+                current.cin = mail.cin;
+            })?;
+
+            return Err("Not enough room in your inventory to claim these attachments".to_string());
+        };
+
+        for item in attachments {
+            let _ = server.items_store.insert(item);
+        }
+
+        Ok(mail)
+    }
+
+    fn deliver(player_id: Uuid, mail: &Mail) -> Result<Vec<Item>, String> {
+        let mut attachments = mail.items.clone();
+
+        if mail.cin > 0 {
+            attachments.push(Item::from_template(player_id, "cin", mail.cin)?);
+        }
+
+        for item in &attachments {
+            item.add_to_empty_slot(SlotKind::Inventory)?;
+        }
+
+        Ok(attachments)
+    }
+
+    pub fn delete(player_id: Uuid, mail_id: Uuid) -> Result<Mail, String> {
+        let server = GameServer::global();
+
+        let mail = Self::find(player_id, mail_id)?;
+
+        if mail.has_attachments() {
+            return Err("Claim the attachments before deleting this mail".to_string());
+        }
+
+        server.mail_store.remove(&mail.id)
+    }
+
+    pub async fn send_list(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::Mailbox,
+            Box::new(Self::list(player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+}
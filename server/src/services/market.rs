@@ -0,0 +1,257 @@
+use crate::messages::{MarketPage, OutgoingEvent, OutgoingMessage};
+use crate::meta::{MARKET_LISTING_FEE_PERCENT, MARKET_PAGE_SIZE, MAX_MARKET_LISTINGS, MAX_MARKET_LISTING_HOURS};
+use crate::models::{Item, ItemKind, ItemTier, MarketListing, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::inventory::Inventory;
+use crate::services::mailbox::Mailbox;
+use uuid::Uuid;
+
+pub struct MarketFilter {
+    pub kind: Option<ItemKind>,
+    pub tier: Option<ItemTier>,
+    pub min_level: Option<u32>,
+    pub max_level: Option<u32>,
+}
+
+pub struct Market;
+
+impl Market {
+    pub fn listing_fee(price: u64) -> Result<u64, String> {
+        let fee = price
+            .checked_mul(MARKET_LISTING_FEE_PERCENT)
+            .ok_or("The buyout price is too high")?;
+
+        Ok((fee / 100).max(1))
+    }
+
+    pub fn list(
+        player_id: Uuid,
+        kind: SlotKind,
+        index: u64,
+        quantity: Option<u64>,
+        price: u64,
+        duration_hours: u64,
+    ) -> Result<MarketListing, String> {
+        let server = GameServer::global();
+
+        if !matches!(kind, SlotKind::Inventory | SlotKind::Consumable) {
+            return Err(format!("Items cannot be listed from a {:?} slot", kind));
+        }
+
+        if price == 0 {
+            return Err("The buyout price must be at least 1 cin".to_string());
+        }
+
+        if duration_hours == 0 || duration_hours > MAX_MARKET_LISTING_HOURS {
+            return Err(format!("Listings can last between 1 and {} hours", MAX_MARKET_LISTING_HOURS));
+        }
+
+        let active = server.market_listings_store.find_all_by(|l| l.seller_id == player_id).len();
+
+        if active >= MAX_MARKET_LISTINGS {
+            return Err(format!("You can have at most {} active listings", MAX_MARKET_LISTINGS));
+        }
+
+        let seller = server.player_store
+            .find_by(|p| p.id == player_id)
+            .ok_or("Player not found")?;
+
+        let slot = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .ok_or("Slot not found")?;
+
+        let item = slot.item.clone().ok_or("No item in slot")?;
+
+        if item.kind == ItemKind::Currency {
+            return Err("Cin cannot be listed on the market".to_string());
+        }
+
+        let quantity = quantity.unwrap_or(item.quantity);
+
+        if quantity == 0 || quantity > item.quantity {
+            return Err(format!("You can list between 1 and {} {}", item.quantity, item.name));
+        }
+
+        let fee = Self::listing_fee(price)?;
+        let available = Inventory::count(player_id, "cin");
+
+        if available < fee {
+            return Err(format!("Not enough cin for the listing fee: {} needed, {} available", fee, available));
+        }
+
+        Inventory::consume(player_id, "cin", fee)?;
+
+        let whole_stack = quantity == item.quantity;
+
+        let listed = Item {
+            id: if whole_stack { item.id } else { Uuid::new_v4() },
+            quantity,
+            ..item.clone()
+        };
+
+        server.slots_store.update(&slot.id, |slot| {
+            if whole_stack {
+                slot.item = None;
+            } else if let Some(item) = slot.item.as_mut() {
+                item.quantity -= quantity;
+            }
+        })?;
+
+        if whole_stack {
+            let _ = server.items_store.remove(&item.id);
+        }
+
+        server.market_listings_store.insert(MarketListing::new(
+            player_id,
+            seller.name,
+            listed,
+            price,
+            fee,
+            duration_hours,
+        ))
+    }
+
+    pub fn buy(player_id: Uuid, listing_id: Uuid) -> Result<MarketListing, String> {
+        let server = GameServer::global();
+
+        let listing = server.market_listings_store
+            .find_by(|l| l.id == listing_id)
+            .ok_or("That listing is no longer available")?;
+
+        if listing.seller_id == player_id {
+            return Err("You cannot buy your own listing".to_string());
+        }
+
+        if listing.is_expired() {
+            return Err("That listing has expired".to_string());
+        }
+
+        let available = Inventory::count(player_id, "cin");
+
+        if available < listing.price {
+            return Err(format!("Not enough cin: {} needed, {} available", listing.price, available));
+        }
+
+        let listing = server.market_listings_store
+            .remove(&listing.id)
+            .map_err(|_| "That listing is no longer available".to_string())?;
+
+        let item = Item {
+            player_id,
+            ..listing.item.clone()
+        };
+
+        let snapshot = Inventory::snapshot(player_id);
+
+        let bought = Inventory::consume(player_id, "cin", listing.price)
+            .and_then(|_| item.add_to_empty_slot(SlotKind::Inventory));
+
+        if let Err(e) = bought {
+            Inventory::restore(snapshot);
+            server.market_listings_store.insert(listing)?;
+
+            return Err(e);
+        }
+
+        let _ = server.items_store.insert(item);
+
+        Ok(listing)
+    }
+
+    pub fn cancel(player_id: Uuid, listing_id: Uuid) -> Result<MarketListing, String> {
+        let server = GameServer::global();
+
+        let listing = server.market_listings_store
+            .find_by(|l| l.id == listing_id && l.seller_id == player_id)
+            .ok_or("Listing not found")?;
+
+        server.market_listings_store.remove(&listing.id)
+    }
+
+    pub fn expired() -> Vec<MarketListing> {
+        let server = GameServer::global();
+
+        server.market_listings_store
+            .find_all_by(|l| l.is_expired())
+            .into_iter()
+            .filter_map(|listing| server.market_listings_store.remove(&listing.id).ok())
+            .collect()
+    }
+
+    pub fn search(filter: &MarketFilter, page: usize) -> MarketPage {
+        let server = GameServer::global();
+
+        let mut listings = server.market_listings_store.find_all_by(|l| {
+            !l.is_expired()
+                && filter.kind.as_ref().is_none_or(|kind| l.item.kind == *kind)
+                && filter.tier.as_ref().is_none_or(|tier| l.item.tier == *tier)
+                && filter.min_level.is_none_or(|level| l.item.level >= level)
+                && filter.max_level.is_none_or(|level| l.item.level <= level)
+        });
+
+        listings.sort_by_key(|l| (l.price, l.created_at));
+
+        let total = listings.len();
+        let total_pages = total.div_ceil(MARKET_PAGE_SIZE).max(1);
+        let page = page.clamp(1, total_pages);
+
+        MarketPage {
+            page,
+            total_pages,
+            total,
+            listings: listings
+                .into_iter()
+                .skip((page - 1) * MARKET_PAGE_SIZE)
+                .take(MARKET_PAGE_SIZE)
+                .collect(),
+        }
+    }
+
+    pub fn own_listings(player_id: Uuid) -> Vec<MarketListing> {
+        let server = GameServer::global();
+
+        let mut listings = server.market_listings_store.find_all_by(|l| l.seller_id == player_id);
+        listings.sort_by_key(|l| l.expires_at);
+
+        listings
+    }
+
+    pub async fn send_own_listings(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::MarketOwnListings,
+            Box::new(Self::own_listings(player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn return_to_seller(listing: MarketListing, subject: &str, body: String) -> Result<(), String> {
+        Mailbox::send(
+            listing.seller_id,
+            "Marketplace",
+            subject.to_string(),
+            body,
+            vec![listing.item.clone()],
+            0,
+        ).await?;
+
+        Self::send_own_listings(listing.seller_id).await;
+
+        Ok(())
+    }
+
+    pub async fn pay_seller(listing: MarketListing, buyer_name: &str) -> Result<(), String> {
+        Mailbox::send(
+            listing.seller_id,
+            "Marketplace",
+            format!("Sold: {} {}", listing.item.quantity, listing.item.name),
+            format!("{} bought your {} {} for {} cin.", buyer_name, listing.item.quantity, listing.item.name, listing.price),
+            vec![],
+            listing.price,
+        ).await?;
+
+        Self::send_own_listings(listing.seller_id).await;
+
+        Ok(())
+    }
+}
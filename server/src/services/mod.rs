@@ -1 +1,22 @@
-pub mod probability_calculator;
\ No newline at end of file
+pub mod probability_calculator;
+pub mod loot_generator;
+pub mod progression;
+pub mod combat;
+pub mod expedition_service;
+pub mod encumbrance;
+pub mod buffs;
+pub mod runes;
+pub mod inventory;
+pub mod enchanting;
+pub mod compass_upgrader;
+pub mod parties;
+pub mod offline_progress;
+pub mod shop;
+pub mod trades;
+pub mod mailbox;
+pub mod market;
+pub mod crafting;
+pub mod salvage;
+pub mod stash;
+pub mod item_destroyer;
+pub mod rewards;
@@ -0,0 +1,344 @@
+use crate::messages::{OfflineLoot, OfflineReport, OutgoingEvent, OutgoingMessage};
+use crate::meta::{Monster, MAX_OFFLINE_SECS};
+use crate::models::{Expedition, ExpeditionEndReason, ExpeditionKind, Item, PlayerResource, PlayerStats};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::combat::{Combat, CombatState, MONSTER_SPAWN_DELAY};
+use crate::services::expedition_service::ExpeditionService;
+use crate::services::loot_generator::LootGenerator;
+use crate::services::probability_calculator::PlayerProbabilities;
+use crate::services::rewards::Rewards;
+use chrono::Utc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+pub struct OfflineProgress;
+
+impl OfflineProgress {
+    pub fn checkpoint(player_id: Uuid) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let on_expedition = server.expeditions_store
+            .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none())
+            .is_some();
+
+        if !on_expedition {
+            return Ok(());
+        }
+
+        let player_state = server.player_state_store
+            .find_by(|state| state.player_id == player_id)
+            .ok_or("Player state not found")?;
+
+        if player_state.offline_since.is_none() {
+            server.player_state_store.update(&player_state.id, |state| {
+                state.offline_since = Some(Utc::now());
+            })?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn catch_up(player_id: Uuid) -> Result<Option<OfflineReport>, String> {
+        let server = GameServer::global();
+
+        let player_state = server.player_state_store
+            .find_by(|state| state.player_id == player_id)
+            .ok_or("Player state not found")?;
+
+        let Some(offline_since) = player_state.offline_since else {
+            return Ok(None);
+        };
+
+        server.player_state_store.update(&player_state.id, |state| {
+            state.offline_since = None;
+        })?;
+
+        let Some(expedition) = server.expeditions_store
+            .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none()) else {
+            return Ok(None);
+        };
+
+        let player = server.player_store
+            .find_by(|p| p.id == player_id)
+            .ok_or("Player not found")?;
+
+        let stats = server.player_stats_store
+            .find_by(|s| s.player_id == player_id)
+            .ok_or("Player stats not found")?;
+
+        let resource = server.player_resource_store
+            .find_by(|r| r.player_id == player_id)
+            .ok_or("Player resource not found")?;
+
+        let away_secs = (Utc::now() - offline_since).num_seconds().max(0) as u64;
+        let start_secs = (offline_since - expedition.started_at).num_seconds().max(0) as u64;
+
+        let energy_cost = ExpeditionService::energy_cost(player_id, &expedition.kind);
+
+        let exp_frequency = player_id.exp_frequency();
+        let exp_chance = player_id.exp_chance();
+        let cin_frequency = player_id.cin_frequency();
+        let cin_chance = player_id.cin_chance();
+        let loot_frequency = player_id.loot_frequency();
+        let loot_chance = player_id.loot_chance();
+        let material_frequency = player_id.material_frequency(&expedition.kind);
+        let material_chance = player_id.material_chance(&expedition.kind);
+        let rare_find_chance = player_id.rare_find_chance(&expedition.kind);
+
+        let mut simulation = Simulation {
+            energy: resource.energy,
+            hp: resource.hp,
+            exp: 0,
+            cin: 0,
+            found: Vec::new(),
+            defeated_by: None,
+        };
+
+        let origin = Instant::now();
+        let mut combat_state = CombatState::Idle { next_spawn: origin + MONSTER_SPAWN_DELAY };
+        let mut simulated_secs = 0;
+        let mut energy_spent = 0;
+
+        while simulated_secs < away_secs.min(MAX_OFFLINE_SECS)
+            && simulation.energy > 0
+            && simulation.defeated_by.is_none() {
+            simulation.regenerate(&resource, &stats, simulated_secs, simulated_secs + 1);
+            simulated_secs += 1;
+
+            let energy = simulation.energy.saturating_sub(energy_cost);
+            energy_spent += simulation.energy - energy;
+            simulation.energy = energy;
+
+            let elapsed_secs = start_secs + simulated_secs;
+
+            if elapsed_secs.is_multiple_of(exp_frequency) && rand::random::<f32>() < exp_chance {
+                simulation.exp += player_id.exp_amount();
+            }
+
+            match expedition.kind {
+                ExpeditionKind::Hunt => {
+                    if elapsed_secs.is_multiple_of(cin_frequency) && rand::random::<f32>() < cin_chance {
+                        simulation.cin += player_id.cin_amount();
+                    }
+
+                    if elapsed_secs.is_multiple_of(loot_frequency)
+                        && rand::random::<f32>() < loot_chance
+                        && let Some(item) = LootGenerator::roll(player_id, &expedition.kind, expedition.difficulty) {
+                        simulation.found.push(item);
+                    }
+
+                    let now = origin + Duration::from_secs(simulated_secs);
+                    combat_state = simulation.fight(player_id, &expedition, player.level, &stats, combat_state, now);
+                }
+                ExpeditionKind::Gathering | ExpeditionKind::Mining | ExpeditionKind::Fishing => {
+                    if !elapsed_secs.is_multiple_of(material_frequency) {
+                        continue;
+                    }
+
+                    if rand::random::<f32>() < material_chance
+                        && let Some(mut material) = LootGenerator::roll(player_id, &expedition.kind, expedition.difficulty) {
+                        material.quantity *= player_id.material_amount();
+                        simulation.found.push(material);
+                    }
+
+                    if rand::random::<f32>() < rare_find_chance
+                        && let Some(rare_find) = LootGenerator::roll_rare_find(player_id, &expedition.kind) {
+                        simulation.found.push(rare_find);
+                    }
+                }
+            }
+        }
+
+        let out_of_energy = simulation.energy == 0;
+
+        // Regeneration is paused while a player is offline, so the time after the
+        // simulation stopped still has to be credited here.
+        simulation.regenerate(&resource, &stats, simulated_secs, away_secs);
+
+        server.player_resource_store.update(&resource.id, |resource| {
+            resource.energy = simulation.energy;
+            resource.hp = simulation.hp;
+        })?;
+
+        ExpeditionService::record(player_id, |summary| summary.energy_spent += energy_spent);
+
+        let mut exp = 0;
+        let mut cin = 0;
+
+        if simulation.exp > 0 {
+            exp = Self::own_share(player_id, Rewards::grant_exp(player_id, simulation.exp).await);
+        }
+
+        if simulation.cin > 0 {
+            cin = Self::own_share(player_id, Rewards::grant_cin(player_id, simulation.cin).await);
+        }
+
+        let mut items = Vec::new();
+        let mut mailed_items = Vec::new();
+
+        for delivered in Rewards::distribute_loot(player_id, simulation.found).await {
+            if delivered.recipient_id != player_id {
+                continue;
+            }
+
+            if delivered.mailed {
+                Self::tally(&mut mailed_items, &delivered.item);
+            } else {
+                Self::tally(&mut items, &delivered.item);
+            }
+        }
+
+        Rewards::auto_loot(player_id).await;
+
+        let defeated_by = simulation.defeated_by.as_ref().map(|monster| monster.name.clone());
+
+        if let Some(monster) = &simulation.defeated_by {
+            ExpeditionService::handle_defeat(expedition.id, player_id, monster).await?;
+        } else if out_of_energy {
+            ExpeditionService::end(
+                expedition.id,
+                player_id,
+                ExpeditionEndReason::OutOfEnergy,
+                "Your expedition ended while you were away due to lack of energy.",
+            ).await?;
+        }
+
+        Ok(Some(OfflineReport {
+            away_secs,
+            simulated_secs,
+            exp,
+            cin,
+            items,
+            mailed_items,
+            energy_spent,
+            expedition_ended: defeated_by.is_some() || out_of_energy,
+            defeated_by,
+        }))
+    }
+
+    fn own_share(player_id: Uuid, shares: Vec<(Uuid, u64)>) -> u64 {
+        shares
+            .into_iter()
+            .filter(|(member_id, _)| *member_id == player_id)
+            .map(|(_, share)| share)
+            .sum()
+    }
+
+    fn tally(loot: &mut Vec<OfflineLoot>, item: &Item) {
+        match loot.iter_mut().find(|entry| entry.template_id == item.template_id) {
+            Some(entry) => entry.quantity += item.quantity,
+            None => loot.push(OfflineLoot {
+                template_id: item.template_id.clone(),
+                name: item.name.clone(),
+                quantity: item.quantity,
+            }),
+        }
+    }
+
+    pub async fn send(player_id: Uuid, report: OfflineReport) {
+        let ws_manager = WebSocketManager::global();
+
+        let found: u64 = report.items
+            .iter()
+            .chain(report.mailed_items.iter())
+            .filter(|item| item.template_id != "cin")
+            .map(|item| item.quantity)
+            .sum();
+
+        ws_manager.send_log_to_player(
+            player_id,
+            format!(
+                "While you were away ({} minutes) you gained {} experience, {} cin and {} items, spending {} energy.",
+                report.simulated_secs / 60, report.exp, report.cin, found, report.energy_spent
+            ),
+        ).await;
+
+        if !report.mailed_items.is_empty() {
+            ws_manager.send_log_to_player(
+                player_id,
+                "Some of your finds were sent to your mailbox because there was no room left on the ground.".to_string(),
+            ).await;
+        }
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::OfflineReport,
+            Box::new(report) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+}
+
+struct Simulation {
+    energy: u64,
+    hp: u64,
+    exp: u64,
+    cin: u64,
+    found: Vec<Item>,
+    defeated_by: Option<Monster>,
+}
+
+impl Simulation {
+    fn regenerate(&mut self, resource: &PlayerResource, stats: &PlayerStats, from_secs: u64, to_secs: u64) {
+        let ticks = |interval: u64| (to_secs * 1000 / interval) - (from_secs * 1000 / interval);
+
+        self.energy = (self.energy + ticks(stats.energy_regeneration_interval) * stats.energy_regeneration)
+            .min(resource.max_energy);
+
+        if self.defeated_by.is_none() {
+            self.hp = (self.hp + ticks(stats.hp_regeneration_interval) * stats.hp_regeneration)
+                .min(resource.max_hp);
+        }
+    }
+
+    fn fight(
+        &mut self,
+        player_id: Uuid,
+        expedition: &Expedition,
+        player_level: u8,
+        stats: &PlayerStats,
+        state: CombatState,
+        now: Instant,
+    ) -> CombatState {
+        let mut encounter = match state {
+            CombatState::Idle { next_spawn } if now < next_spawn => {
+                return CombatState::Idle { next_spawn };
+            }
+            CombatState::Idle { .. } => {
+                return match Combat::spawn(expedition, player_level, stats, now) {
+                    Some(encounter) => CombatState::Fighting(encounter),
+                    None => CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY },
+                };
+            }
+            CombatState::Fighting(encounter) => encounter,
+        };
+
+        while encounter.next_player_attack.min(encounter.next_monster_attack) <= now {
+            if encounter.next_player_attack <= encounter.next_monster_attack {
+                let damage = Combat::damage(stats.attack, encounter.monster.defense);
+                encounter.monster_hp = encounter.monster_hp.saturating_sub(damage);
+                encounter.next_player_attack += Duration::from_millis(stats.attack_speed);
+
+                if encounter.monster_hp == 0 {
+                    self.exp += encounter.monster.exp_reward;
+
+                    if rand::random::<f32>() < encounter.monster.loot_chance
+                        && let Some(loot_item) = LootGenerator::roll(player_id, &expedition.kind, expedition.difficulty) {
+                        self.found.push(loot_item);
+                    }
+
+                    return CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY };
+                }
+            } else {
+                let damage = Combat::damage(encounter.monster.attack, stats.defense);
+                self.hp = self.hp.saturating_sub(damage);
+                encounter.next_monster_attack += Duration::from_millis(encounter.monster.attack_speed);
+
+                if self.hp == 0 {
+                    self.defeated_by = Some(encounter.monster);
+                    return CombatState::Idle { next_spawn: now + MONSTER_SPAWN_DELAY };
+                }
+            }
+        }
+
+        CombatState::Fighting(encounter)
+    }
+}
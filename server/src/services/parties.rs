@@ -0,0 +1,334 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage, PartyInfo, PartyMember};
+use crate::meta::{MAX_PARTY_SIZE, PARTY_INVITE_SECS};
+use crate::models::{Item, Party, PartyInvitation, PartyLootRule};
+use crate::server::{GameServer, WebSocketManager};
+use rand::seq::SliceRandom;
+use uuid::Uuid;
+
+pub struct Parties;
+
+impl Parties {
+    pub fn of(player_id: Uuid) -> Option<Party> {
+        let server = GameServer::global();
+
+        server.parties_store.find_by(|party| party.members.contains(&player_id))
+    }
+
+    pub fn invite(player_id: Uuid, target_name: &str) -> Result<(Party, Uuid), String> {
+        let server = GameServer::global();
+
+        let target = server.player_store
+            .find_by(|p| p.name == target_name)
+            .ok_or(format!("Player '{}' not found", target_name))?;
+
+        if target.id == player_id {
+            return Err("You cannot invite yourself".to_string());
+        }
+
+        if Self::of(target.id).is_some() {
+            return Err(format!("{} is already in a party", target.name));
+        }
+
+        let existing = Self::of(player_id);
+
+        if let Some(party) = &existing {
+            if !party.is_leader(player_id) {
+                return Err("Only the party leader can invite players".to_string());
+            }
+
+            if party.members.len() + party.pending_invites() >= MAX_PARTY_SIZE {
+                return Err(format!("A party can have at most {} members", MAX_PARTY_SIZE));
+            }
+
+            if party.is_invited(target.id) {
+                return Err(format!("{} has already been invited", target.name));
+            }
+        }
+
+        let party = match existing {
+            Some(party) => party,
+            None => server.parties_store.insert(Party::new(player_id))?,
+        };
+
+        let party = server.parties_store.update(&party.id, |party| {
+            party.invites.retain(|invite| invite.player_id != target.id && !invite.is_expired());
+            party.invites.push(PartyInvitation::new(target.id, PARTY_INVITE_SECS));
+        })?;
+
+        Ok((party, target.id))
+    }
+
+    pub fn accept(player_id: Uuid, party_id: Uuid) -> Result<Party, String> {
+        let server = GameServer::global();
+
+        if Self::of(player_id).is_some() {
+            return Err("You are already in a party".to_string());
+        }
+
+        let party = server.parties_store
+            .find_by(|party| party.id == party_id)
+            .ok_or("Party not found")?;
+
+        if !party.is_invited(player_id) {
+            return Err("You have no pending invite to this party".to_string());
+        }
+
+        if party.members.len() >= MAX_PARTY_SIZE {
+            return Err("The party is full".to_string());
+        }
+
+        server.parties_store.update(&party.id, |party| {
+            party.invites.retain(|invite| invite.player_id != player_id);
+            party.members.push(player_id);
+        })
+    }
+
+    pub fn decline(player_id: Uuid, party_id: Uuid) -> Result<Party, String> {
+        let server = GameServer::global();
+
+        let party = server.parties_store
+            .find_by(|party| party.id == party_id && party.invites.iter().any(|invite| invite.player_id == player_id))
+            .ok_or("You have no pending invite to this party")?;
+
+        server.parties_store.update(&party.id, |party| {
+            party.invites.retain(|invite| invite.player_id != player_id);
+        })
+    }
+
+    pub async fn withdraw_invites(player_id: Uuid) {
+        let server = GameServer::global();
+
+        let parties = server.parties_store.find_all_by(|party| {
+            !party.members.contains(&player_id) && party.invites.iter().any(|invite| invite.player_id == player_id)
+        });
+
+        for party in parties {
+            if let Ok(party) = server.parties_store.update(&party.id, |party| {
+                party.invites.retain(|invite| invite.player_id != player_id);
+            }) {
+                Self::settle(party).await;
+            }
+        }
+    }
+
+    pub async fn expire_invites() {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let parties = server.parties_store.find_all_by(|party| party.invites.iter().any(|invite| invite.is_expired()));
+
+        for party in parties {
+            let expired: Vec<Uuid> = party.invites
+                .iter()
+                .filter(|invite| invite.is_expired())
+                .map(|invite| invite.player_id)
+                .collect();
+
+            let Ok(party) = server.parties_store.update(&party.id, |party| {
+                party.invites.retain(|invite| !invite.is_expired());
+            }) else {
+                continue;
+            };
+
+            for invitee_id in expired {
+                if let Some(invitee) = server.player_store.find_by(|p| p.id == invitee_id) {
+                    Self::send_log(&party, format!("The party invite to {} expired.", invitee.name)).await;
+                }
+
+                ws_manager.send_log_to_player(invitee_id, "A party invite you received has expired.".to_string()).await;
+            }
+
+            Self::settle(party).await;
+        }
+    }
+
+    pub async fn settle(party: Party) {
+        let server = GameServer::global();
+
+        if party.is_abandoned() {
+            let _ = server.parties_store.remove(&party.id);
+
+            for member in &party.members {
+                Self::send_to(*member, None).await;
+            }
+        } else {
+            Self::send(&party).await;
+        }
+    }
+
+    pub fn remove_member(party: &Party, player_id: Uuid) -> Result<Option<Party>, String> {
+        let server = GameServer::global();
+
+        let remaining: Vec<Uuid> = party.members
+            .iter()
+            .copied()
+            .filter(|id| *id != player_id)
+            .collect();
+
+        if remaining.len() <= 1 {
+            server.parties_store.remove(&party.id)?;
+            return Ok(None);
+        }
+
+        let updated = server.parties_store.update(&party.id, |party| {
+            party.members = remaining.clone();
+
+            if party.leader_id == player_id {
+                party.leader_id = remaining[0];
+            }
+        })?;
+
+        Ok(Some(updated))
+    }
+
+    pub fn set_loot_rule(player_id: Uuid, loot_rule: PartyLootRule) -> Result<Party, String> {
+        let server = GameServer::global();
+
+        let party = Self::of(player_id).ok_or("You are not in a party")?;
+
+        if !party.is_leader(player_id) {
+            return Err("Only the party leader can change the loot rule".to_string());
+        }
+
+        server.parties_store.update(&party.id, |party| {
+            party.loot_rule = loot_rule;
+        })
+    }
+
+    pub fn expedition_mates(player_id: Uuid) -> Vec<Uuid> {
+        let server = GameServer::global();
+
+        let Some(party) = Self::of(player_id) else {
+            return vec![player_id];
+        };
+
+        let Some(expedition) = server.expeditions_store
+            .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none()) else {
+            return vec![player_id];
+        };
+
+        expedition.participants
+            .into_iter()
+            .filter(|id| party.members.contains(id))
+            .collect()
+    }
+
+    pub fn split(player_id: Uuid, amount: u64) -> Vec<(Uuid, u64)> {
+        let mates = Self::expedition_mates(player_id);
+        let share = amount / mates.len() as u64;
+        let remainder = amount % mates.len() as u64;
+
+        mates
+            .into_iter()
+            .map(|id| if id == player_id { (id, share + remainder) } else { (id, share) })
+            .filter(|(_, amount)| *amount > 0)
+            .collect()
+    }
+
+    pub fn loot_recipient(player_id: Uuid, item: &Item) -> Uuid {
+        let server = GameServer::global();
+
+        let Some(party) = Self::of(player_id) else {
+            return player_id;
+        };
+
+        let mates = Self::expedition_mates(player_id);
+
+        match party.loot_rule {
+            PartyLootRule::Equal => player_id,
+            PartyLootRule::RoundRobin => {
+                let recipient = mates[party.round_robin_index % mates.len()];
+
+                let _ = server.parties_store.update(&party.id, |party| {
+                    party.round_robin_index = party.round_robin_index.wrapping_add(1);
+                });
+
+                recipient
+            }
+            PartyLootRule::NeedGreed => {
+                let needers: Vec<Uuid> = mates
+                    .iter()
+                    .copied()
+                    .filter(|id| Self::needs(*id, item))
+                    .collect();
+
+                let candidates = if needers.is_empty() { &mates } else { &needers };
+
+                candidates
+                    .choose(&mut rand::thread_rng())
+                    .copied()
+                    .unwrap_or(player_id)
+            }
+        }
+    }
+
+    fn needs(player_id: Uuid, item: &Item) -> bool {
+        let server = GameServer::global();
+
+        let Some(slot_kind) = item.kind.slot_kind() else {
+            return false;
+        };
+
+        let player = server.player_store.find_by(|p| p.id == player_id);
+        let attributes = server.player_attributes_store.find_by(|attr| attr.player_id == player_id);
+
+        match (player, attributes) {
+            (Some(player), Some(attributes)) => item.can_equip(&slot_kind, player.level, &attributes).is_ok(),
+            _ => false,
+        }
+    }
+
+    pub fn info(party: &Party) -> PartyInfo {
+        let server = GameServer::global();
+
+        let name_of = |player_id: &Uuid| {
+            server.player_store
+                .find_by(|p| p.id == *player_id)
+                .map(|p| (p.name, p.level))
+        };
+
+        PartyInfo {
+            id: party.id,
+            leader_id: party.leader_id,
+            loot_rule: party.loot_rule,
+            members: party.members
+                .iter()
+                .filter_map(|id| {
+                    name_of(id).map(|(name, level)| PartyMember {
+                        player_id: *id,
+                        name,
+                        level,
+                    })
+                })
+                .collect(),
+            invites: party.invites
+                .iter()
+                .filter(|invite| !invite.is_expired())
+                .filter_map(|invite| name_of(&invite.player_id).map(|(name, _)| name))
+                .collect(),
+        }
+    }
+
+    pub async fn send(party: &Party) {
+        for member in &party.members {
+            Self::send_to(*member, Some(party)).await;
+        }
+    }
+
+    pub async fn send_to(player_id: Uuid, party: Option<&Party>) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::Party,
+            Box::new(party.map(Self::info)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn send_log(party: &Party, text: String) {
+        let ws_manager = WebSocketManager::global();
+
+        for member in &party.members {
+            ws_manager.send_log_to_player(*member, text.clone()).await;
+        }
+    }
+}
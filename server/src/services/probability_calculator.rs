@@ -1,6 +1,7 @@
-use crate::meta::level_to_exp;
-use crate::models::{Item, Player, PlayerAttributes, PlayerStats, Slot};
+use crate::models::{BuffKind, ExpeditionKind, Item, Player, PlayerAttributes, PlayerStats, RuneTotals, Slot};
 use crate::server::GameServer;
+use crate::services::buffs::Buffs;
+use crate::services::runes::Runes;
 use rand::Rng;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -10,13 +11,17 @@ pub enum ProbabilityType {
     ExpGain,
     LootDrop,
     CinGain,
+    MaterialYield(ExpeditionKind),
+    RareFind(ExpeditionKind),
 }
 
 #[derive(Debug, Clone)]
+#[allow(clippy::enum_variant_names)]
 pub enum FrequencyType {
     ExpRoll,
     LootRoll,
     CinRoll,
+    MaterialRoll(ExpeditionKind),
 }
 
 pub struct ProbabilityCalculator;
@@ -29,6 +34,8 @@ impl ProbabilityCalculator {
             ProbabilityType::ExpGain => Self::calculate_exp_gain(&context),
             ProbabilityType::LootDrop => Self::calculate_loot_drop(&context),
             ProbabilityType::CinGain => Self::calculate_cin_gain(&context),
+            ProbabilityType::MaterialYield(kind) => Self::calculate_material_yield(&context, &kind),
+            ProbabilityType::RareFind(kind) => Self::calculate_rare_find(&context, &kind),
         }
     }
 
@@ -39,15 +46,20 @@ impl ProbabilityCalculator {
             FrequencyType::ExpRoll => Self::calculate_exp_frequency(&context),
             FrequencyType::LootRoll => Self::calculate_loot_frequency(&context),
             FrequencyType::CinRoll => Self::calculate_cin_frequency(&context),
+            FrequencyType::MaterialRoll(kind) => Self::calculate_material_frequency(&context, &kind),
         }
     }
 
     pub fn exp_gain_chance(player_id: Uuid) -> f32 {
-        Self::calculate(player_id, ProbabilityType::ExpGain).unwrap_or(0.1)
+        let chance = Self::calculate(player_id, ProbabilityType::ExpGain).unwrap_or(0.1);
+
+        (chance + Buffs::total(player_id, BuffKind::ExpChance)).clamp(0.0001, 1.0)
     }
 
     pub fn loot_drop_chance(player_id: Uuid) -> f32 {
-        Self::calculate(player_id, ProbabilityType::LootDrop).unwrap_or(0.05)
+        let chance = Self::calculate(player_id, ProbabilityType::LootDrop).unwrap_or(0.05);
+
+        (chance + Buffs::total(player_id, BuffKind::LootChance)).clamp(0.0001, 1.0)
     }
 
     pub fn exp_roll_frequency(player_id: Uuid) -> u64 {
@@ -74,6 +86,22 @@ impl ProbabilityCalculator {
         Self::get_cin_amount(player_id).unwrap_or(5)
     }
 
+    pub fn material_yield_chance(player_id: Uuid, kind: &ExpeditionKind) -> f32 {
+        Self::calculate(player_id, ProbabilityType::MaterialYield(kind.clone())).unwrap_or(0.3)
+    }
+
+    pub fn rare_find_chance(player_id: Uuid, kind: &ExpeditionKind) -> f32 {
+        Self::calculate(player_id, ProbabilityType::RareFind(kind.clone())).unwrap_or(0.01)
+    }
+
+    pub fn material_roll_frequency(player_id: Uuid, kind: &ExpeditionKind) -> u64 {
+        Self::calculate_frequency(player_id, FrequencyType::MaterialRoll(kind.clone())).unwrap_or(15)
+    }
+
+    pub fn calculate_material_amount(player_id: Uuid) -> u64 {
+        Self::get_material_amount(player_id).unwrap_or(1)
+    }
+
     fn gather_player_context(player_id: Uuid) -> Result<PlayerContext, String> {
         let server = GameServer::global();
 
@@ -93,7 +121,7 @@ impl ProbabilityCalculator {
             slot.player_id == player_id && slot.is_equipment_slot() && slot.item.is_some()
         });
 
-        let level = Self::calculate_level(player.exp);
+        let level = player.level;
 
         let compass = server.slots_store
             .find_by(|slot| {
@@ -110,26 +138,16 @@ impl ProbabilityCalculator {
             equipment,
             level,
             compass,
+            runes: Runes::totals(player_id),
         })
     }
 
-    fn calculate_level(exp: u64) -> u8 {
-        let level_map = level_to_exp();
-
-        for (level, required_exp) in level_map.iter() {
-            if exp < *required_exp {
-                return level.saturating_sub(1).max(1);
-            }
-        }
-
-        level_map.keys().max().copied().unwrap_or(1)
-    }
-
     fn calculate_weighted_probability(
         context: &PlayerContext,
         base_chance: f32,
         weights: &HashMap<&str, f32>,
         equipment_modifier: f32,
+        rune_bonus: f32,
     ) -> Result<f32, String> {
         let attr_score =
             (context.attributes.strength as f32 * weights.get("strength").unwrap_or(&1.0)) +
@@ -151,9 +169,9 @@ impl ProbabilityCalculator {
             0.0
         };
 
-        let final_probability = base_chance + (attr_score * 0.0001) + equipment_bonus + level_bonus - compass_penalty;
+        let final_probability = base_chance + (attr_score * 0.0001) + equipment_bonus + rune_bonus + level_bonus - compass_penalty;
 
-        Ok(final_probability.max(0.0001).min(1.0))
+        Ok(final_probability.clamp(0.0001, 1.0))
     }
 
     fn calculate_weighted_frequency(
@@ -172,7 +190,7 @@ impl ProbabilityCalculator {
 
         let equipment_bonus = Self::calculate_equipment_bonus(&context.equipment, equipment_modifier);
 
-        let level_bonus = (context.level as f32 - 1.0);
+        let level_bonus = context.level as f32 - 1.0;
 
         let compass_penalty = if let Some(compass) = &context.compass && compass.enchanted > 0 && compass.level > 1 {
             let level_penalty = compass.level as f32;
@@ -238,7 +256,7 @@ impl ProbabilityCalculator {
             ("luck", 2.2),
         ]);
 
-        Self::calculate_weighted_probability(context, 0.1, &weights, 1.0)
+        Self::calculate_weighted_probability(context, 0.1, &weights, 1.0, context.runes.exp_chance)
     }
 
     fn calculate_exp_frequency(context: &PlayerContext) -> Result<u64, String> {
@@ -292,7 +310,7 @@ impl ProbabilityCalculator {
             ("luck", 3.0),
         ]);
 
-        Self::calculate_weighted_probability(context, 0.04, &weights, 1.2)
+        Self::calculate_weighted_probability(context, 0.04, &weights, 1.2, context.runes.loot_chance)
     }
 
     fn calculate_loot_frequency(context: &PlayerContext) -> Result<u64, String> {
@@ -318,7 +336,7 @@ impl ProbabilityCalculator {
             ("luck", 2.5),
         ]);
 
-        Self::calculate_weighted_probability(context, 0.12, &weights, 1.1)
+        Self::calculate_weighted_probability(context, 0.12, &weights, 1.1, context.runes.cin_chance)
     }
 
     fn calculate_cin_frequency(context: &PlayerContext) -> Result<u64, String> {
@@ -334,6 +352,90 @@ impl ProbabilityCalculator {
         Self::calculate_weighted_frequency(context, 20, &weights, 0.9)
     }
 
+    fn material_weights(kind: &ExpeditionKind) -> HashMap<&'static str, f32> {
+        match kind {
+            ExpeditionKind::Hunt => HashMap::new(),
+            ExpeditionKind::Gathering => HashMap::from([
+                ("strength", 0.6),
+                ("dexterity", 1.2),
+                ("vitality", 0.9),
+                ("intelligence", 1.8),
+                ("spirit", 2.0),
+                ("luck", 1.2),
+            ]),
+            ExpeditionKind::Mining => HashMap::from([
+                ("strength", 2.2),
+                ("dexterity", 0.8),
+                ("vitality", 1.8),
+                ("intelligence", 0.6),
+                ("spirit", 0.7),
+                ("luck", 1.0),
+            ]),
+            ExpeditionKind::Fishing => HashMap::from([
+                ("strength", 0.7),
+                ("dexterity", 2.0),
+                ("vitality", 0.8),
+                ("intelligence", 1.0),
+                ("spirit", 1.6),
+                ("luck", 1.8),
+            ]),
+        }
+    }
+
+    fn calculate_material_yield(context: &PlayerContext, kind: &ExpeditionKind) -> Result<f32, String> {
+        let weights = Self::material_weights(kind);
+
+        Self::calculate_weighted_probability(context, 0.35, &weights, 0.8, 0.0)
+    }
+
+    fn calculate_rare_find(context: &PlayerContext, kind: &ExpeditionKind) -> Result<f32, String> {
+        let mut weights = Self::material_weights(kind);
+        weights.insert("luck", 3.0);
+
+        Self::calculate_weighted_probability(context, 0.01, &weights, 1.0, context.runes.loot_chance)
+    }
+
+    fn calculate_material_frequency(context: &PlayerContext, kind: &ExpeditionKind) -> Result<u64, String> {
+        let weights = Self::material_weights(kind);
+
+        let base_interval = match kind {
+            ExpeditionKind::Hunt => 24,
+            ExpeditionKind::Gathering => 12,
+            ExpeditionKind::Mining => 16,
+            ExpeditionKind::Fishing => 20,
+        };
+
+        Self::calculate_weighted_frequency(context, base_interval, &weights, 0.8)
+    }
+
+    fn get_material_amount(player_id: Uuid) -> Result<u64, String> {
+        let server = GameServer::global();
+
+        let compass_slot = server.slots_store
+            .find_by(|slot| {
+                slot.player_id == player_id
+                    && slot.kind == crate::models::SlotKind::Compass
+                    && slot.item.is_some()
+            })
+            .ok_or("No compass equipped")?;
+
+        let compass = compass_slot.item.as_ref().unwrap();
+
+        let base_amount = 1u64;
+
+        let level_bonus = compass.level as u64 / 2;
+
+        let enchant_bonus = compass.enchanted as u64 / 3;
+
+        let total_amount = base_amount + level_bonus + enchant_bonus;
+
+        let mut rng = rand::thread_rng();
+        let variance_factor = rng.gen_range(0.5..=1.5);
+        let final_amount = (total_amount as f64 * variance_factor).round() as u64;
+
+        Ok(final_amount.max(1))
+    }
+
     fn get_cin_amount(player_id: Uuid) -> Result<u64, String> {
         let server = GameServer::global();
 
@@ -351,7 +453,7 @@ impl ProbabilityCalculator {
 
         let level_bonus = compass.level as u64 * 2;
 
-        let enchant_bonus = compass.enchanted as u64 * 1;
+        let enchant_bonus = compass.enchanted as u64;
 
         let total_cin = base_cin + level_bonus + enchant_bonus;
 
@@ -363,6 +465,7 @@ impl ProbabilityCalculator {
     }
 }
 
+#[allow(dead_code)]
 struct PlayerContext {
     player: Player,
     attributes: PlayerAttributes,
@@ -370,6 +473,7 @@ struct PlayerContext {
     equipment: Vec<Slot>,
     level: u8,
     compass: Option<Item>,
+    runes: RuneTotals,
 }
 
 pub trait PlayerProbabilities {
@@ -381,6 +485,10 @@ pub trait PlayerProbabilities {
     fn cin_chance(&self) -> f32;
     fn cin_frequency(&self) -> u64;
     fn cin_amount(&self) -> u64;
+    fn material_chance(&self, kind: &ExpeditionKind) -> f32;
+    fn rare_find_chance(&self, kind: &ExpeditionKind) -> f32;
+    fn material_frequency(&self, kind: &ExpeditionKind) -> u64;
+    fn material_amount(&self) -> u64;
 }
 
 impl PlayerProbabilities for Uuid {
@@ -415,4 +523,20 @@ impl PlayerProbabilities for Uuid {
     fn cin_amount(&self) -> u64 {
         ProbabilityCalculator::calculate_cin_amount(*self)
     }
+
+    fn material_chance(&self, kind: &ExpeditionKind) -> f32 {
+        ProbabilityCalculator::material_yield_chance(*self, kind)
+    }
+
+    fn rare_find_chance(&self, kind: &ExpeditionKind) -> f32 {
+        ProbabilityCalculator::rare_find_chance(*self, kind)
+    }
+
+    fn material_frequency(&self, kind: &ExpeditionKind) -> u64 {
+        ProbabilityCalculator::material_roll_frequency(*self, kind)
+    }
+
+    fn material_amount(&self) -> u64 {
+        ProbabilityCalculator::calculate_material_amount(*self)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+use crate::messages::{LevelUp, OutgoingEvent, OutgoingMessage, PlayerInfo};
+use crate::meta::{exp_for_level, level_for_exp, ATTRIBUTE_POINTS_PER_LEVEL};
+use crate::models::Player;
+use crate::server::{GameServer, WebSocketManager};
+use uuid::Uuid;
+
+pub struct Progression;
+
+impl Progression {
+    pub async fn gain_exp(player_id: Uuid, amount: u64) -> Result<Player, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let player = server.player_store
+            .find_by(|p| p.id == player_id)
+            .ok_or("Player not found")?;
+
+        let updated = server.player_store.update(&player.id, |p| {
+            p.exp += amount;
+            p.level = level_for_exp(p.exp);
+        })?;
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::PlayerInfo,
+            Box::new(PlayerInfo::new(updated.clone())) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        if updated.level > player.level {
+            Self::level_up(&updated, player.level).await?;
+        }
+
+        Ok(updated)
+    }
+
+    pub async fn lose_exp_percent(player_id: Uuid, percent: u64) -> Result<u64, String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let player = server.player_store
+            .find_by(|p| p.id == player_id)
+            .ok_or("Player not found")?;
+
+        let mut penalty = 0;
+
+        let updated = server.player_store.update(&player.id, |p| {
+            let level = level_for_exp(p.exp);
+            let level_floor = exp_for_level(level);
+            let level_span = exp_for_level(level + 1).saturating_sub(level_floor);
+
+            penalty = (level_span * percent / 100).min(p.exp - level_floor);
+
+            p.exp -= penalty;
+            p.level = level;
+        })?;
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::PlayerInfo,
+            Box::new(PlayerInfo::new(updated)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        Ok(penalty)
+    }
+
+    async fn level_up(player: &Player, previous_level: u8) -> Result<(), String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let levels_gained = (player.level - previous_level) as u32;
+        let awarded_points = levels_gained * ATTRIBUTE_POINTS_PER_LEVEL;
+
+        let attributes = server.player_attributes_store
+            .find_by(|attr| attr.player_id == player.id)
+            .ok_or("Player attributes not found")?;
+
+        let updated_attributes = server.player_attributes_store.update(&attributes.id, |attr| {
+            attr.unspent_points += awarded_points;
+        })?;
+
+        ws_manager.send_to_player(player.id, OutgoingMessage::new(
+            OutgoingEvent::LevelUp,
+            Box::new(LevelUp {
+                level: player.level,
+                attribute_points: updated_attributes.unspent_points,
+            }) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_to_player(player.id, OutgoingMessage::new(
+            OutgoingEvent::PlayerAttributes,
+            Box::new(updated_attributes) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+
+        ws_manager.send_log_to_player(
+            player.id,
+            format!("You reached level {}! You gained {} attribute points.", player.level, awarded_points),
+        ).await;
+
+        Ok(())
+    }
+}
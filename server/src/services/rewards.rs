@@ -0,0 +1,293 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage};
+use crate::models::{Item, Slot, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::encumbrance::Encumbrance;
+use crate::services::expedition_service::ExpeditionService;
+use crate::services::mailbox::Mailbox;
+use crate::services::parties::Parties;
+use crate::services::progression::Progression;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub struct Delivered {
+    pub recipient_id: Uuid,
+    pub item: Item,
+    pub mailed: bool,
+}
+
+pub struct Rewards;
+
+impl Rewards {
+    pub async fn grant_exp(finder_id: Uuid, exp_amount: u64) -> Vec<(Uuid, u64)> {
+        let ws_manager = WebSocketManager::global();
+
+        let mut granted = Vec::new();
+
+        for (member_id, share) in Parties::split(finder_id, exp_amount) {
+            if Progression::gain_exp(member_id, share).await.is_ok() {
+                ExpeditionService::record(member_id, |summary| summary.exp += share);
+
+                ws_manager.send_to_player(member_id, OutgoingMessage::new(
+                    OutgoingEvent::GainedExperience,
+                    Box::new(share) as Box<dyn erased_serde::Serialize + Send>,
+                )).await;
+
+                let message = if member_id == finder_id {
+                    format!("You gained {} experience!", share)
+                } else {
+                    format!("You gained {} experience from your party!", share)
+                };
+
+                ws_manager.send_log_to_player(member_id, message).await;
+
+                granted.push((member_id, share));
+            }
+        }
+
+        granted
+    }
+
+    pub async fn grant_cin(finder_id: Uuid, cin_amount: u64) -> Vec<(Uuid, u64)> {
+        let mut granted = Vec::new();
+
+        for (member_id, share) in Parties::split(finder_id, cin_amount) {
+            if Self::deliver_cin(member_id, share).await {
+                granted.push((member_id, share));
+            }
+        }
+
+        granted
+    }
+
+    async fn deliver_cin(player_id: Uuid, cin_amount: u64) -> bool {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let Ok(cin_item) = Item::from_template(player_id, "cin", cin_amount) else {
+            return false;
+        };
+
+        match cin_item.add_to_empty_slot(SlotKind::Ground) {
+            Ok(_) => {
+                if server.items_store.insert(cin_item).is_err() {
+                    return false;
+                }
+
+                ExpeditionService::record(player_id, |summary| summary.cin += cin_amount);
+
+                Self::send_slots(player_id).await;
+
+                ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                    OutgoingEvent::GainedCin,
+                    Box::new(cin_amount) as Box<dyn erased_serde::Serialize + Send>,
+                )).await;
+
+                ws_manager.send_log_to_player(
+                    player_id,
+                    format!("You found {} cin!", cin_amount),
+                ).await;
+
+                true
+            }
+            Err(_) => {
+                let mailed = Mailbox::send(
+                    player_id,
+                    "Expedition",
+                    format!("{} cin", cin_amount),
+                    "There was no room left on the ground for this cin.".to_string(),
+                    vec![],
+                    cin_amount,
+                ).await;
+
+                if mailed.is_err() {
+                    return false;
+                }
+
+                ExpeditionService::record(player_id, |summary| summary.cin += cin_amount);
+
+                ws_manager.send_log_to_player(
+                    player_id,
+                    format!("You found {} cin but there is no room to drop it. It was sent to your mailbox.", cin_amount),
+                ).await;
+
+                true
+            }
+        }
+    }
+
+    pub async fn distribute_loot(finder_id: Uuid, items: Vec<Item>) -> Vec<Delivered> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let mut delivered = Vec::new();
+        let mut overflow: HashMap<Uuid, Vec<Item>> = HashMap::new();
+
+        for mut item in items {
+            let recipient_id = Parties::loot_recipient(finder_id, &item);
+
+            if recipient_id != finder_id {
+                item.player_id = recipient_id;
+
+                if let Some(recipient) = server.player_store.find_by(|p| p.id == recipient_id) {
+                    ws_manager.send_log_to_player(
+                        finder_id,
+                        format!("{} {} went to {} by party loot rules.", item.quantity, item.name, recipient.name),
+                    ).await;
+                }
+            }
+
+            if item.add_to_empty_slot(SlotKind::Ground).is_err() {
+                overflow.entry(recipient_id).or_default().push(item);
+                continue;
+            }
+
+            if server.items_store.insert(item.clone()).is_err() {
+                continue;
+            }
+
+            ExpeditionService::record(recipient_id, |summary| summary.items_found += item.quantity);
+
+            ws_manager.send_to_player(recipient_id, OutgoingMessage::new(
+                OutgoingEvent::GainedItem,
+                Box::new(item.clone()) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+
+            ws_manager.send_log_to_player(
+                recipient_id,
+                format!("You found {} {}!", item.quantity, item.name),
+            ).await;
+
+            delivered.push(Delivered { recipient_id, item, mailed: false });
+        }
+
+        let mut recipients: Vec<Uuid> = delivered.iter().map(|d| d.recipient_id).collect();
+        recipients.sort();
+        recipients.dedup();
+
+        for recipient_id in recipients {
+            Self::send_slots(recipient_id).await;
+        }
+
+        for (recipient_id, items) in overflow {
+            let (subject, body, message) = match items.as_slice() {
+                [item] => (
+                    format!("{} {}", item.quantity, item.name),
+                    "There was no room left on the ground for this find.",
+                    format!(
+                        "You found {} {} but there is no room to drop it. It was sent to your mailbox.",
+                        item.quantity, item.name
+                    ),
+                ),
+                _ => (
+                    "Finds from your expedition".to_string(),
+                    "There was no room left on the ground for these finds.",
+                    format!("{} finds did not fit on the ground and were sent to your mailbox.", items.len()),
+                ),
+            };
+
+            let mailed = Mailbox::send(
+                recipient_id,
+                "Expedition",
+                subject,
+                body.to_string(),
+                items.clone(),
+                0,
+            ).await;
+
+            if mailed.is_err() {
+                continue;
+            }
+
+            let quantity: u64 = items.iter().map(|item| item.quantity).sum();
+            ExpeditionService::record(recipient_id, |summary| summary.items_found += quantity);
+
+            ws_manager.send_log_to_player(recipient_id, message).await;
+
+            delivered.extend(items.into_iter().map(|item| Delivered { recipient_id, item, mailed: true }));
+        }
+
+        delivered
+    }
+
+    async fn send_slots(player_id: Uuid) {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let mut slots = server.slots_store.find_all_by(|slot| slot.player_id == player_id);
+        slots.sort_by_key(|slot| slot.index);
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::Slots,
+            Box::new(slots) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn auto_loot(player_id: Uuid) {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let player_state = server.player_state_store
+            .find_by(|state| state.player_id == player_id);
+
+        let Some(state) = player_state else {
+            return;
+        };
+
+        if !state.is_looting {
+            return;
+        }
+
+        let ground_slots_with_items: Vec<Slot> = server.slots_store
+            .find_all_by(|slot| {
+                slot.player_id == player_id
+                    && slot.kind == SlotKind::Ground
+                    && slot.item.is_some()
+            });
+
+        let mut slots_updated = false;
+
+        for ground_slot in ground_slots_with_items {
+            if let Some(item) = &ground_slot.item {
+                match item.add_to_empty_slot(SlotKind::Inventory) {
+                    Ok(_) => {
+                        let _ = server.items_store.insert(item.clone());
+
+                        let _ = server.slots_store.update(&ground_slot.id, |slot| {
+                            slot.item = None;
+                        });
+
+                        slots_updated = true;
+
+                        ws_manager.send_log_to_player(
+                            player_id,
+                            format!("Looted {} {}", item.quantity, item.name),
+                        ).await;
+                    }
+                    Err(e) => {
+                        ws_manager.send_log_to_player(
+                            player_id,
+                            format!("Could not loot {}: {}", item.name, e),
+                        ).await;
+                    }
+                }
+            }
+        }
+
+        if slots_updated {
+            let mut all_slots = server.slots_store.find_all_by(|slot| slot.player_id == player_id);
+            all_slots.sort_by_key(|slot| slot.index);
+
+            ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                OutgoingEvent::Slots,
+                Box::new(all_slots) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+
+            if let Ok(resource) = Encumbrance::refresh(player_id) {
+                ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                    OutgoingEvent::PlayerResource,
+                    Box::new(resource) as Box<dyn erased_serde::Serialize + Send>,
+                )).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage, RuneBreakdown, RuneContribution, RuneSetContribution};
+use crate::meta::rune_set_bonuses;
+use crate::models::{RuneFamily, RuneTotals, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub struct Runes;
+
+impl Runes {
+    pub fn breakdown(player_id: Uuid) -> RuneBreakdown {
+        let server = GameServer::global();
+
+        let mut rune_slots = server.slots_store
+            .find_all_by(|slot| slot.player_id == player_id && slot.kind == SlotKind::Rune && slot.item.is_some());
+
+        rune_slots.sort_by_key(|slot| slot.index);
+
+        let mut totals = RuneTotals::default();
+        let mut runes = Vec::new();
+        let mut family_counts: BTreeMap<RuneFamily, u32> = BTreeMap::new();
+
+        for slot in rune_slots {
+            let Some(item) = slot.item else {
+                continue;
+            };
+
+            let Some(rune) = item.rune else {
+                continue;
+            };
+
+            for modifier in &rune.modifiers {
+                totals.add(modifier);
+            }
+
+            *family_counts.entry(rune.family).or_insert(0) += 1;
+
+            runes.push(RuneContribution {
+                index: slot.index,
+                name: item.name,
+                family: rune.family,
+                modifiers: rune.modifiers,
+            });
+        }
+
+        let mut sets = Vec::new();
+
+        for (family, equipped) in family_counts {
+            for bonus in rune_set_bonuses(family) {
+                let active = equipped >= bonus.pieces;
+
+                if active {
+                    for modifier in &bonus.modifiers {
+                        totals.add(modifier);
+                    }
+                }
+
+                sets.push(RuneSetContribution {
+                    family,
+                    equipped,
+                    pieces: bonus.pieces,
+                    active,
+                    modifiers: bonus.modifiers,
+                });
+            }
+        }
+
+        RuneBreakdown {
+            runes,
+            sets,
+            totals,
+        }
+    }
+
+    pub fn totals(player_id: Uuid) -> RuneTotals {
+        Self::breakdown(player_id).totals
+    }
+
+    pub async fn send(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::RuneBreakdown,
+            Box::new(Self::breakdown(player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+}
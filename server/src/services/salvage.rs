@@ -0,0 +1,175 @@
+use crate::meta::{is_salvageable, salvage_yield, SALVAGE_UNDO_SECS};
+use crate::models::{Item, ItemTier, SalvageRecord, SalvagedItem, Slot, SlotKind};
+use crate::server::GameServer;
+use crate::services::inventory::Inventory;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub struct Salvage;
+
+impl Salvage {
+    pub fn salvage(player_id: Uuid, kind: SlotKind, index: u64) -> Result<SalvageRecord, String> {
+        let server = GameServer::global();
+
+        if kind != SlotKind::Inventory {
+            return Err("Only items in your inventory can be salvaged".to_string());
+        }
+
+        let slot = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .ok_or("Slot not found")?;
+
+        let item = slot.item.as_ref().ok_or("No item in slot")?;
+
+        if !is_salvageable(&item.kind) {
+            return Err(format!("{} cannot be salvaged", item.name));
+        }
+
+        Self::apply(player_id, vec![slot])
+    }
+
+    pub fn salvage_below_tier(player_id: Uuid, tier: ItemTier) -> Result<SalvageRecord, String> {
+        let server = GameServer::global();
+
+        let slots = server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id
+                && slot.kind == SlotKind::Inventory
+                && slot.item.as_ref().is_some_and(|item| is_salvageable(&item.kind) && item.tier < tier)
+        });
+
+        if slots.is_empty() {
+            return Err(format!("You have no items below {:?} tier to salvage", tier));
+        }
+
+        Self::apply(player_id, slots)
+    }
+
+    fn apply(player_id: Uuid, slots: Vec<Slot>) -> Result<SalvageRecord, String> {
+        let server = GameServer::global();
+
+        let snapshot = Inventory::snapshot(player_id);
+
+        let mut salvaged = Vec::new();
+        let mut materials: BTreeMap<String, u64> = BTreeMap::new();
+
+        for slot in slots {
+            let Some(item) = slot.item else {
+                continue;
+            };
+
+            for (template_id, quantity) in salvage_yield(&item) {
+                *materials.entry(template_id).or_default() += quantity;
+            }
+
+            server.slots_store.update(&slot.id, |slot| {
+                slot.item = None;
+            })?;
+
+            salvaged.push(SalvagedItem {
+                item,
+                kind: slot.kind,
+                index: slot.index,
+            });
+        }
+
+        let mut produced = Vec::new();
+
+        for (template_id, quantity) in &materials {
+            let material = Item::from_template(player_id, template_id, *quantity)
+                .and_then(|material| material.add_to_empty_slot(SlotKind::Inventory).map(|_| material));
+
+            match material {
+                Ok(material) => produced.push(material),
+                Err(_) => {
+                    Inventory::restore(snapshot);
+                    return Err("Not enough room in your inventory for the salvaged materials".to_string());
+                }
+            }
+        }
+
+        for entry in &salvaged {
+            let _ = server.items_store.remove(&entry.item.id);
+        }
+
+        for material in produced {
+            let _ = server.items_store.insert(material);
+        }
+
+        server.salvage_records_store.insert(SalvageRecord::new(
+            player_id,
+            salvaged,
+            materials.into_iter().collect(),
+            SALVAGE_UNDO_SECS,
+        ))
+    }
+
+    pub fn undo(player_id: Uuid, salvage_id: Uuid) -> Result<SalvageRecord, String> {
+        let server = GameServer::global();
+
+        let record = server.salvage_records_store
+            .find_by(|record| record.id == salvage_id && record.player_id == player_id)
+            .ok_or("There is no salvage to undo")?;
+
+        if !record.can_undo() {
+            return Err("The undo window for this salvage has passed".to_string());
+        }
+
+        for (template_id, quantity) in &record.materials {
+            if Inventory::count(player_id, template_id) < *quantity {
+                return Err("You no longer have all of the salvaged materials".to_string());
+            }
+        }
+
+        let snapshot = Inventory::snapshot(player_id);
+
+        let restored = record.materials
+            .iter()
+            .try_for_each(|(template_id, quantity)| Inventory::consume(player_id, template_id, *quantity))
+            .and_then(|_| {
+                record.salvaged
+                    .iter()
+                    .try_for_each(|entry| Self::return_item(player_id, entry))
+            });
+
+        if let Err(e) = restored {
+            Inventory::restore(snapshot);
+            return Err(format!("The salvage could not be undone: {}", e));
+        }
+
+        for entry in &record.salvaged {
+            let _ = server.items_store.insert(entry.item.clone());
+        }
+
+        server.salvage_records_store.remove(&record.id)
+    }
+
+    fn return_item(player_id: Uuid, entry: &SalvagedItem) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let original = server.slots_store.find_by(|slot| {
+            slot.player_id == player_id
+                && slot.kind == entry.kind
+                && slot.index == entry.index
+                && slot.item.is_none()
+        });
+
+        match original {
+            Some(slot) => {
+                server.slots_store.update(&slot.id, |slot| {
+                    slot.item = Some(entry.item.clone());
+                })?;
+
+                Ok(())
+            }
+            None => entry.item.add_to_empty_slot(SlotKind::Inventory),
+        }
+    }
+
+    pub fn expire() {
+        let server = GameServer::global();
+
+        for record in server.salvage_records_store.find_all_by(|record| !record.can_undo()) {
+            let _ = server.salvage_records_store.remove(&record.id);
+        }
+    }
+}
@@ -0,0 +1,306 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage, ShopItem};
+use crate::meta::{sell_price, ItemCatalog, ShopCatalog, ShopListing, BUYBACK_LIMIT};
+use crate::models::{Buyback, Item, ShopStock, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::inventory::Inventory;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+pub struct Shop;
+
+impl Shop {
+    fn stock(template_id: &str) -> Option<ShopStock> {
+        let server = GameServer::global();
+
+        server.shop_stock_store.find_by(|stock| stock.template_id == template_id)
+    }
+
+    fn remaining(listing: &ShopListing) -> Option<u64> {
+        let stock = listing.stock?;
+
+        Some(Self::stock(&listing.template_id).map_or(stock, |s| s.remaining))
+    }
+
+    fn reserve_stock(template_id: &str, name: &str, full: u64, quantity: u64) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let stock = match Self::stock(template_id) {
+            Some(stock) => stock,
+            None => server.shop_stock_store.insert(ShopStock::new(template_id.to_string(), full))?,
+        };
+
+        let mut remaining = None;
+
+        server.shop_stock_store.update(&stock.id, |stock| {
+            let Some(left) = stock.remaining.checked_sub(quantity) else {
+                remaining = Some(stock.remaining);
+                return;
+            };
+
+            if stock.remaining == full {
+                stock.restocked_at = Utc::now();
+            }
+
+            stock.remaining = left;
+        })?;
+
+        match remaining {
+            Some(remaining) => Err(format!("Only {} {} left in stock", remaining, name)),
+            None => Ok(()),
+        }
+    }
+
+    fn release_stock(template_id: &str, full: u64, quantity: u64) {
+        let server = GameServer::global();
+
+        if let Some(stock) = Self::stock(template_id) {
+            let _ = server.shop_stock_store.update(&stock.id, |stock| {
+                stock.remaining = (stock.remaining + quantity).min(full);
+            });
+        }
+    }
+
+    fn ensure_cin(player_id: Uuid, cost: u64) -> Result<(), String> {
+        let available = Inventory::count(player_id, "cin");
+
+        if available < cost {
+            return Err(format!("Not enough cin: {} needed, {} available", cost, available));
+        }
+
+        Ok(())
+    }
+
+    pub fn buy(player_id: Uuid, template_id: &str, quantity: u64) -> Result<(Item, u64), String> {
+        let listing = ShopCatalog::global()
+            .get(template_id)
+            .ok_or("That item is not sold here")?;
+
+        let template = ItemCatalog::global()
+            .get(template_id)
+            .ok_or_else(|| format!("Unknown item template '{}'", template_id))?;
+
+        if quantity == 0 {
+            return Err("Quantity must be at least 1".to_string());
+        }
+
+        if !template.is_stackable && quantity > 1 {
+            return Err(format!("{} can only be bought one at a time", template.name));
+        }
+
+        if let Some(remaining) = Self::remaining(listing)
+            && remaining < quantity {
+            return Err(format!("Only {} {} left in stock", remaining, template.name));
+        }
+
+        let cost = listing.price
+            .checked_mul(quantity)
+            .ok_or("Quantity is too large")?;
+
+        Self::ensure_cin(player_id, cost)?;
+
+        if let Some(full) = listing.stock {
+            Self::reserve_stock(template_id, &template.name, full, quantity)?;
+        }
+
+        let snapshot = Inventory::snapshot(player_id);
+
+        let bought = Inventory::consume(player_id, "cin", cost)
+            .and_then(|_| Inventory::credit(player_id, template_id, quantity));
+
+        let item = match bought {
+            Ok(item) => item,
+            Err(e) => {
+                Inventory::restore(snapshot);
+
+                if let Some(full) = listing.stock {
+                    Self::release_stock(template_id, full, quantity);
+                }
+
+                return Err(e);
+            }
+        };
+
+        Ok((item, cost))
+    }
+
+    pub fn sell(player_id: Uuid, kind: SlotKind, index: u64, quantity: Option<u64>) -> Result<(Item, u64), String> {
+        let server = GameServer::global();
+
+        if !matches!(kind, SlotKind::Inventory | SlotKind::Consumable) {
+            return Err(format!("Items cannot be sold from a {:?} slot", kind));
+        }
+
+        let slot = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .ok_or("Slot not found")?;
+
+        let item = slot.item.clone().ok_or("No item in slot")?;
+        let quantity = quantity.unwrap_or(item.quantity);
+
+        if quantity == 0 || quantity > item.quantity {
+            return Err(format!("You can sell between 1 and {} {}", item.quantity, item.name));
+        }
+
+        let whole_stack = quantity == item.quantity;
+
+        let sold = Item {
+            id: if whole_stack { item.id } else { Uuid::new_v4() },
+            quantity,
+            ..item.clone()
+        };
+
+        let price = sell_price(&sold).ok_or(format!("{} cannot be sold", item.name))?;
+
+        server.slots_store.update(&slot.id, |slot| {
+            if whole_stack {
+                slot.item = None;
+            } else if let Some(item) = slot.item.as_mut() {
+                item.quantity -= quantity;
+            }
+        })?;
+
+        if let Err(e) = Inventory::credit(player_id, "cin", price) {
+            server.slots_store.update(&slot.id, |slot| {
+                slot.item = Some(item.clone());
+            })?;
+
+            return Err(e);
+        }
+
+        if whole_stack {
+            let _ = server.items_store.remove(&item.id);
+        }
+
+        server.buybacks_store.insert(Buyback::new(player_id, sold.clone(), price))?;
+
+        let mut buybacks = Self::buybacks(player_id);
+
+        for expired in buybacks.drain(..).skip(BUYBACK_LIMIT) {
+            let _ = server.buybacks_store.remove(&expired.id);
+        }
+
+        Ok((sold, price))
+    }
+
+    pub fn buyback(player_id: Uuid, buyback_id: Uuid) -> Result<Buyback, String> {
+        let server = GameServer::global();
+
+        let buyback = server.buybacks_store
+            .find_by(|b| b.id == buyback_id && b.player_id == player_id)
+            .ok_or("That item is no longer available for buyback")?;
+
+        Self::ensure_cin(player_id, buyback.price)?;
+
+        let buyback = server.buybacks_store
+            .remove(&buyback.id)
+            .map_err(|_| "That item is no longer available for buyback".to_string())?;
+
+        let snapshot = Inventory::snapshot(player_id);
+
+        let bought = Inventory::consume(player_id, "cin", buyback.price)
+            .and_then(|_| buyback.item.add_to_empty_slot(SlotKind::Inventory));
+
+        if let Err(e) = bought {
+            Inventory::restore(snapshot);
+            server.buybacks_store.insert(buyback)?;
+
+            return Err(e);
+        }
+
+        let _ = server.items_store.insert(buyback.item.clone());
+
+        Ok(buyback)
+    }
+
+    pub fn restock() -> bool {
+        let server = GameServer::global();
+        let catalog = ShopCatalog::global();
+
+        let mut restocked = false;
+
+        for stock in server.shop_stock_store.find_all_by(|_| true) {
+            let Some(listing) = catalog.get(&stock.template_id) else {
+                continue;
+            };
+
+            let (Some(full), Some(restock_secs)) = (listing.stock, listing.restock_secs) else {
+                continue;
+            };
+
+            let due = stock.restocked_at + Duration::seconds(restock_secs as i64) <= Utc::now();
+
+            if stock.remaining < full && due {
+                restocked |= server.shop_stock_store.update(&stock.id, |stock| {
+                    stock.remaining = full;
+                    stock.restocked_at = Utc::now();
+                }).is_ok();
+            }
+        }
+
+        restocked
+    }
+
+    pub fn listings() -> Vec<ShopItem> {
+        let item_catalog = ItemCatalog::global();
+
+        ShopCatalog::global()
+            .listings()
+            .filter_map(|listing| {
+                let template = item_catalog.get(&listing.template_id)?;
+                let stock = Self::stock(&listing.template_id);
+
+                let restocks_at = match (&stock, listing.stock, listing.restock_secs) {
+                    (Some(stock), Some(full), Some(restock_secs)) if stock.remaining < full => {
+                        Some(stock.restocked_at + Duration::seconds(restock_secs as i64))
+                    }
+                    _ => None,
+                };
+
+                Some(ShopItem {
+                    template_id: listing.template_id.clone(),
+                    name: template.name.clone(),
+                    price: listing.price,
+                    stock: listing.stock,
+                    remaining: Self::remaining(listing),
+                    restocks_at,
+                })
+            })
+            .collect()
+    }
+
+    pub fn buybacks(player_id: Uuid) -> Vec<Buyback> {
+        let server = GameServer::global();
+
+        let mut buybacks = server.buybacks_store.find_all_by(|b| b.player_id == player_id);
+        buybacks.sort_by_key(|b| std::cmp::Reverse(b.sold_at));
+
+        buybacks
+    }
+
+    pub async fn send_listings(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::ShopListings,
+            Box::new(Self::listings()) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn broadcast_listings() {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.broadcast_to_all(OutgoingMessage::new(
+            OutgoingEvent::ShopListings,
+            Box::new(Self::listings()) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn send_buybacks(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::ShopBuybacks,
+            Box::new(Self::buybacks(player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+}
@@ -0,0 +1,96 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage, StashInfo};
+use crate::meta::{MAX_STASH_TABS, STASH_TAB_BASE_COST, STASH_TAB_SIZE};
+use crate::models::{Slot, SlotKind};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::inventory::Inventory;
+use uuid::Uuid;
+
+pub struct Stash;
+
+impl Stash {
+    pub fn ensure_accessible(player_id: Uuid) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let has_active_expedition = server.expeditions_store
+            .find_by(|e| e.participants.contains(&player_id) && e.ended_at.is_none())
+            .is_some();
+
+        if has_active_expedition {
+            return Err("Your stash cannot be reached during an expedition".to_string());
+        }
+
+        Ok(())
+    }
+
+    pub fn tabs(player_id: Uuid) -> u64 {
+        let server = GameServer::global();
+
+        let slots = server.slots_store.find_all_by(|slot| {
+            slot.player_id == player_id && slot.kind == SlotKind::Stash
+        });
+
+        slots.len() as u64 / STASH_TAB_SIZE
+    }
+
+    pub fn next_tab_cost(tabs: u64) -> Option<u64> {
+        (tabs < MAX_STASH_TABS).then(|| STASH_TAB_BASE_COST * tabs.max(1))
+    }
+
+    fn add_tab(player_id: Uuid, tab: u64) -> Result<(), String> {
+        let server = GameServer::global();
+
+        for index in tab * STASH_TAB_SIZE..(tab + 1) * STASH_TAB_SIZE {
+            server.slots_store.insert(Slot::new(player_id, SlotKind::Stash, index))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn ensure_slots(player_id: Uuid) -> Result<(), String> {
+        if Self::tabs(player_id) == 0 {
+            Self::add_tab(player_id, 0)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn buy_tab(player_id: Uuid) -> Result<u64, String> {
+        Self::ensure_accessible(player_id)?;
+
+        let tabs = Self::tabs(player_id);
+
+        let cost = Self::next_tab_cost(tabs)
+            .ok_or(format!("You already own the maximum of {} stash tabs", MAX_STASH_TABS))?;
+
+        let available = Inventory::count(player_id, "cin");
+
+        if available < cost {
+            return Err(format!("Not enough cin for a new stash tab: {} needed, {} available", cost, available));
+        }
+
+        Inventory::consume(player_id, "cin", cost)?;
+        Self::add_tab(player_id, tabs)?;
+
+        Ok(cost)
+    }
+
+    pub fn info(player_id: Uuid) -> StashInfo {
+        let tabs = Self::tabs(player_id);
+
+        StashInfo {
+            tabs,
+            max_tabs: MAX_STASH_TABS,
+            tab_size: STASH_TAB_SIZE,
+            next_tab_cost: Self::next_tab_cost(tabs),
+        }
+    }
+
+    pub async fn send(player_id: Uuid) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::StashInfo,
+            Box::new(Self::info(player_id)) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+}
@@ -0,0 +1,372 @@
+use crate::messages::{OutgoingEvent, OutgoingMessage, TradeInfo, TradeOfferInfo};
+use crate::meta::MAX_TRADE_ITEMS;
+use crate::models::{Item, ItemKind, Slot, SlotKind, Trade};
+use crate::server::{GameServer, WebSocketManager};
+use crate::services::encumbrance::Encumbrance;
+use crate::services::inventory::Inventory;
+use uuid::Uuid;
+
+pub struct Trades;
+
+impl Trades {
+    pub fn of(player_id: Uuid) -> Option<Trade> {
+        let server = GameServer::global();
+
+        server.trades_store.find_by(|trade| trade.involves(player_id))
+    }
+
+    fn open(player_id: Uuid) -> Result<Trade, String> {
+        let trade = Self::of(player_id).ok_or("You are not trading with anyone")?;
+
+        if !trade.accepted {
+            return Err("The trade has not been accepted yet".to_string());
+        }
+
+        Ok(trade)
+    }
+
+    pub fn request(player_id: Uuid, target_name: &str) -> Result<(Trade, Uuid), String> {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        let target = server.player_store
+            .find_by(|p| p.name == target_name)
+            .ok_or(format!("Player '{}' not found", target_name))?;
+
+        if target.id == player_id {
+            return Err("You cannot trade with yourself".to_string());
+        }
+
+        if !ws_manager.is_connected(target.id) {
+            return Err(format!("{} is not online", target.name));
+        }
+
+        if Self::of(player_id).is_some() {
+            return Err("You are already trading".to_string());
+        }
+
+        if Self::of(target.id).is_some() {
+            return Err(format!("{} is already trading", target.name));
+        }
+
+        let trade = server.trades_store.insert(Trade::new(player_id, target.id))?;
+
+        Ok((trade, target.id))
+    }
+
+    pub fn accept(player_id: Uuid, trade_id: Uuid) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = server.trades_store
+            .find_by(|trade| trade.id == trade_id && trade.partner_id == player_id)
+            .ok_or("Trade request not found")?;
+
+        if trade.accepted {
+            return Err("You have already accepted this trade".to_string());
+        }
+
+        server.trades_store.update(&trade.id, |trade| {
+            trade.accepted = true;
+        })
+    }
+
+    pub fn add_item(player_id: Uuid, kind: SlotKind, index: u64) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = Self::open(player_id)?;
+
+        if !matches!(kind, SlotKind::Inventory | SlotKind::Consumable) {
+            return Err(format!("Items cannot be traded from a {:?} slot", kind));
+        }
+
+        let item = server.slots_store
+            .find_by(|slot| slot.player_id == player_id && slot.kind == kind && slot.index == index)
+            .and_then(|slot| slot.item)
+            .ok_or("No item in slot")?;
+
+        if item.kind == ItemKind::Currency {
+            return Err("Offer cin by setting an amount instead".to_string());
+        }
+
+        let offer = trade.offer(player_id);
+
+        if offer.items.iter().any(|offered| offered.id == item.id) {
+            return Err(format!("{} is already in your offer", item.name));
+        }
+
+        if offer.items.len() >= MAX_TRADE_ITEMS {
+            return Err(format!("You can offer at most {} items", MAX_TRADE_ITEMS));
+        }
+
+        server.trades_store.update(&trade.id, |trade| {
+            trade.offer_mut(player_id).items.push(item);
+            trade.reset_locks();
+        })
+    }
+
+    pub fn remove_item(player_id: Uuid, item_id: Uuid) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = Self::open(player_id)?;
+
+        if !trade.offer(player_id).items.iter().any(|item| item.id == item_id) {
+            return Err("That item is not in your offer".to_string());
+        }
+
+        server.trades_store.update(&trade.id, |trade| {
+            trade.offer_mut(player_id).items.retain(|item| item.id != item_id);
+            trade.reset_locks();
+        })
+    }
+
+    pub fn set_cin(player_id: Uuid, amount: u64) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = Self::open(player_id)?;
+
+        let available = Inventory::count(player_id, "cin");
+
+        if amount > available {
+            return Err(format!("Not enough cin: {} offered, {} available", amount, available));
+        }
+
+        server.trades_store.update(&trade.id, |trade| {
+            trade.offer_mut(player_id).cin = amount;
+            trade.reset_locks();
+        })
+    }
+
+    pub fn lock(player_id: Uuid) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = Self::open(player_id)?;
+
+        if trade.offer(player_id).locked {
+            return Err("Your offer is already locked".to_string());
+        }
+
+        server.trades_store.update(&trade.id, |trade| {
+            trade.offer_mut(player_id).locked = true;
+        })
+    }
+
+    pub fn confirm(player_id: Uuid) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = Self::open(player_id)?;
+
+        if !trade.both_locked() {
+            return Err("Both offers must be locked before confirming".to_string());
+        }
+
+        server.trades_store.update(&trade.id, |trade| {
+            trade.offer_mut(player_id).confirmed = true;
+        })
+    }
+
+    pub fn cancel(player_id: Uuid) -> Result<Trade, String> {
+        let server = GameServer::global();
+
+        let trade = Self::of(player_id).ok_or("You are not trading with anyone")?;
+
+        server.trades_store.remove(&trade.id)
+    }
+
+    pub fn execute(trade: &Trade) -> Result<(), String> {
+        let server = GameServer::global();
+
+        let sides = [
+            (trade.requester_id, trade.partner_id),
+            (trade.partner_id, trade.requester_id),
+        ];
+
+        for (owner_id, _) in sides {
+            Self::verify_offer(trade, owner_id)?;
+        }
+
+        let snapshot: Vec<Slot> = server.slots_store
+            .find_all_by(|slot| trade.involves(slot.player_id));
+
+        let result = sides
+            .iter()
+            .try_for_each(|(owner_id, _)| Self::withdraw_offer(trade, *owner_id))
+            .and_then(|_| {
+                sides
+                    .iter()
+                    .try_for_each(|(owner_id, receiver_id)| Self::deliver_offer(trade, *owner_id, *receiver_id))
+            });
+
+        if let Err(e) = result {
+            for slot in snapshot {
+                let _ = server.slots_store.update(&slot.id, |current| {
+                    *current = slot.clone();
+                });
+
+                if let Some(item) = slot.item {
+                    let _ = server.items_store.insert(item);
+                }
+            }
+
+            let _ = server.trades_store.update(&trade.id, |trade| trade.reset_locks());
+
+            return Err(format!("The trade could not be completed: {}", e));
+        }
+
+        server.trades_store.remove(&trade.id)?;
+
+        Ok(())
+    }
+
+    fn verify_offer(trade: &Trade, owner_id: Uuid) -> Result<(), String> {
+        let server = GameServer::global();
+        let offer = trade.offer(owner_id);
+
+        for offered in &offer.items {
+            let current = server.slots_store
+                .find_by(|slot| {
+                    slot.player_id == owner_id
+                        && matches!(slot.kind, SlotKind::Inventory | SlotKind::Consumable)
+                        && slot.item.as_ref().is_some_and(|item| item.id == offered.id)
+                })
+                .and_then(|slot| slot.item);
+
+            let unchanged = current.is_some_and(|item| {
+                item.quantity == offered.quantity && item.enchanted == offered.enchanted
+            });
+
+            if !unchanged {
+                let _ = server.trades_store.update(&trade.id, |trade| trade.reset_locks());
+                return Err(format!("{} is no longer available as offered. Locks have been reset.", offered.name));
+            }
+        }
+
+        if Inventory::count(owner_id, "cin") < offer.cin {
+            let _ = server.trades_store.update(&trade.id, |trade| trade.reset_locks());
+            return Err("An offer no longer has enough cin. Locks have been reset.".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn withdraw_offer(trade: &Trade, owner_id: Uuid) -> Result<(), String> {
+        let server = GameServer::global();
+        let offer = trade.offer(owner_id);
+
+        for offered in &offer.items {
+            let slot = server.slots_store
+                .find_by(|slot| {
+                    slot.player_id == owner_id
+                        && slot.item.as_ref().is_some_and(|item| item.id == offered.id)
+                })
+                .ok_or(format!("{} is missing", offered.name))?;
+
+            server.slots_store.update(&slot.id, |slot| {
+                slot.item = None;
+            })?;
+        }
+
+        if offer.cin > 0 {
+            Inventory::consume(owner_id, "cin", offer.cin)?;
+        }
+
+        Ok(())
+    }
+
+    fn deliver_offer(trade: &Trade, owner_id: Uuid, receiver_id: Uuid) -> Result<(), String> {
+        let server = GameServer::global();
+        let offer = trade.offer(owner_id);
+
+        for offered in &offer.items {
+            let item = Item {
+                player_id: receiver_id,
+                ..offered.clone()
+            };
+
+            item.add_to_empty_slot(SlotKind::Inventory)?;
+            server.items_store.insert(item)?;
+        }
+
+        if offer.cin > 0 {
+            Inventory::credit(receiver_id, "cin", offer.cin)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn info(trade: &Trade, player_id: Uuid) -> TradeInfo {
+        let server = GameServer::global();
+
+        let partner_id = trade.other(player_id);
+
+        let offer_info = |owner_id: Uuid| {
+            let offer = trade.offer(owner_id);
+
+            TradeOfferInfo {
+                items: offer.items.clone(),
+                cin: offer.cin,
+                locked: offer.locked,
+                confirmed: offer.confirmed,
+            }
+        };
+
+        TradeInfo {
+            id: trade.id,
+            partner_id,
+            partner_name: server.player_store
+                .find_by(|p| p.id == partner_id)
+                .map(|p| p.name)
+                .unwrap_or_default(),
+            accepted: trade.accepted,
+            own_offer: offer_info(player_id),
+            partner_offer: offer_info(partner_id),
+        }
+    }
+
+    pub async fn send(trade: &Trade) {
+        for player_id in [trade.requester_id, trade.partner_id] {
+            Self::send_to(player_id, Some(trade)).await;
+        }
+    }
+
+    pub async fn send_to(player_id: Uuid, trade: Option<&Trade>) {
+        let ws_manager = WebSocketManager::global();
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::Trade,
+            Box::new(trade.map(|trade| Self::info(trade, player_id))) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn send_inventory(player_id: Uuid) {
+        let server = GameServer::global();
+        let ws_manager = WebSocketManager::global();
+
+        if let Ok(resource) = Encumbrance::refresh(player_id) {
+            ws_manager.send_to_player(player_id, OutgoingMessage::new(
+                OutgoingEvent::PlayerResource,
+                Box::new(resource) as Box<dyn erased_serde::Serialize + Send>,
+            )).await;
+        }
+
+        let mut slots = server.slots_store.find_all_by(|slot| slot.player_id == player_id);
+        slots.sort_by_key(|slot| slot.index);
+
+        ws_manager.send_to_player(player_id, OutgoingMessage::new(
+            OutgoingEvent::Slots,
+            Box::new(slots) as Box<dyn erased_serde::Serialize + Send>,
+        )).await;
+    }
+
+    pub async fn cancel_for(player_id: Uuid, reason: &str) {
+        let ws_manager = WebSocketManager::global();
+
+        let Ok(trade) = Self::cancel(player_id) else {
+            return;
+        };
+
+        let partner_id = trade.other(player_id);
+
+        Self::send_to(partner_id, None).await;
+        ws_manager.send_log_to_player(partner_id, reason.to_string()).await;
+    }
+}
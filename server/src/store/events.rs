@@ -4,4 +4,5 @@ use crate::models::Model;
 pub enum Change<T: Model> {
     Created(T),
     Updated(T),
+    Deleted(T),
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 mod events;
 mod persistence;
+mod schema;
 
 pub use events::Change;
+pub use schema::ensure_schema;
 
 use crate::models::Model;
 use crate::store::persistence::PersistenceLayer;
@@ -76,6 +78,19 @@ impl<T: Model> Store<T> {
         Ok(updated)
     }
     
+    pub fn remove(&self, id: &Uuid) -> Result<T, String> {
+        let (_, removed) = self.data.remove(id)
+            .ok_or_else(|| "Item not found".to_string())?;
+
+        if let Some(ref persistence) = self.persistence {
+            persistence.delete(*id)?;
+        }
+
+        let _ = self.events.send(Change::Deleted(removed.clone()));
+
+        Ok(removed)
+    }
+
     pub fn find_by<F>(&self, predicate: F) -> Option<T>
     where
         F: Fn(&T) -> bool,
@@ -27,6 +27,18 @@ impl PersistenceLayer {
         Ok(())
     }
 
+    pub fn delete(&self, id: Uuid) -> Result<(), String> {
+        let key = format!("{}:{}", self.prefix, id);
+
+        self.db.remove(key)
+            .map_err(|e| e.to_string())?;
+
+        self.db.flush()
+            .map_err(|e| e.to_string())?;
+
+        Ok(())
+    }
+
     pub fn load_all<T: DeserializeOwned>(&self) -> Result<Vec<(Uuid, T)>, String> {
         let prefix = format!("{}:", self.prefix);
         let mut items = Vec::new();
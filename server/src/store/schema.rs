@@ -0,0 +1,43 @@
+use sled::Db;
+
+// Records are stored with bincode, which is not self-describing: adding or
+// reordering a field on any persisted model makes older records undecodable.
+// Bump this whenever a persisted model changes shape.
+pub const SCHEMA_VERSION: u32 = 2;
+pub const RESET_GAME_DATA_ENV: &str = "RESET_GAME_DATA";
+
+const SCHEMA_VERSION_KEY: &str = "__schema_version";
+
+pub fn ensure_schema(db: &Db) -> Result<(), String> {
+    let stored = db.get(SCHEMA_VERSION_KEY)
+        .map_err(|e| e.to_string())?
+        .map(|bytes| bincode::deserialize::<u32>(&bytes))
+        .transpose()
+        .map_err(|e| e.to_string())?;
+
+    match stored {
+        Some(version) if version == SCHEMA_VERSION => return Ok(()),
+        None if db.is_empty() => {}
+        _ => {
+            let found = stored.map_or("an unversioned schema".to_string(), |v| format!("schema version {}", v));
+
+            if std::env::var(RESET_GAME_DATA_ENV).is_ok_and(|v| v == "1") {
+                println!("Resetting game data written with {} (expected schema version {})", found, SCHEMA_VERSION);
+                db.clear().map_err(|e| e.to_string())?;
+            } else {
+                return Err(format!(
+                    "Game data was written with {} but this build expects schema version {}. \
+                     Back up ./game_data and restart with {}=1 to reset it.",
+                    found, SCHEMA_VERSION, RESET_GAME_DATA_ENV
+                ));
+            }
+        }
+    }
+
+    let bytes = bincode::serialize(&SCHEMA_VERSION).map_err(|e| e.to_string())?;
+
+    db.insert(SCHEMA_VERSION_KEY, bytes).map_err(|e| e.to_string())?;
+    db.flush().map_err(|e| e.to_string())?;
+
+    Ok(())
+}